@@ -19,7 +19,13 @@ fn main() {
         }
     };
 
-    println!("Graph: {:?}", graph);
+    print!("{}", graph.to_dot());
+
+    let home = graph.id("home").unwrap();
+    let school = graph.id("school").unwrap();
+    if let Some((cost, path)) = graph.shortest_path(home, school) {
+        println!("Home to School costs {cost} over {} stops", path.len() - 1);
+    }
 
     for (_, node) in graph.iter() {
         println!("{:?}", node)
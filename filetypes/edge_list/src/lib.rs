@@ -0,0 +1,189 @@
+//! Plain text edge lists, one connection per line as `from to weight`.
+//!
+//! Fields are separated by whitespace, and everything after a `#` is a comment.
+//! The weight is optional and defaults to `1.0`, NaN and infinite weights are
+//! rejected. A line with only a name declares a node without connections.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    io::{BufRead, BufReader, Error as IoError, Read, Write},
+};
+
+use thiserror::Error;
+use utils_core::graph::{EdgeType, Graph, Node, NodeID};
+
+#[derive(Error, Debug)]
+pub enum EdgeListError {
+    #[error("I/O error: {0}")]
+    Io(#[from] IoError),
+
+    #[error("Expected `from to [weight]` at line {line}, found {count} fields")]
+    FieldCount { count: usize, line: usize },
+
+    #[error("Invalid weight `{value}` at line {line}")]
+    InvalidWeight { value: String, line: usize },
+
+    #[error("Node name `{name}` can't be written to an edge list")]
+    InvalidName { name: String },
+
+    #[error("Node name `{name}` is used more than once")]
+    DuplicateName { name: String },
+}
+
+/// Reads an edge list, naming every node after its first appearance.
+///
+/// The names can be looked up with [`Graph::id`].
+pub fn read<R, Ty>(reader: R) -> Result<Graph<String, f64, Ty>, EdgeListError>
+where
+    R: Read,
+    Ty: EdgeType,
+{
+    let mut graph = Graph::new();
+    let node = |graph: &mut Graph<String, f64, Ty>, name: &str| match graph.id(name) {
+        Some(id) => id,
+        None => graph.insert_named(name, Node::new(name.to_string())),
+    };
+
+    for (index, line) in BufReader::new(reader).lines().enumerate() {
+        let line = line?;
+        let content = line.split('#').next().unwrap_or_default();
+        let fields: Vec<&str> = content.split_whitespace().collect();
+
+        match fields[..] {
+            [] => {}
+            [name] => {
+                node(&mut graph, name);
+            }
+            [from, to, ref weight @ ..] if weight.len() <= 1 => {
+                let weight = match weight.first() {
+                    Some(value) => value
+                        .parse::<f64>()
+                        .ok()
+                        .filter(|weight| weight.is_finite())
+                        .ok_or_else(|| EdgeListError::InvalidWeight {
+                            value: value.to_string(),
+                            line: index + 1,
+                        })?,
+                    None => 1.0,
+                };
+                let from_id = node(&mut graph, from);
+                let to_id = node(&mut graph, to);
+                graph.connect(from_id, to_id, weight);
+            }
+            _ => {
+                return Err(EdgeListError::FieldCount {
+                    count: fields.len(),
+                    line: index + 1,
+                });
+            }
+        }
+    }
+
+    Ok(graph)
+}
+
+/// Writes the graph as an edge list, naming every node after its value.
+///
+/// Node names have to be unique and can't be empty or contain whitespace or `#`.
+pub fn write<N, E, Ty, W>(graph: &Graph<N, E, Ty>, mut writer: W) -> Result<(), EdgeListError>
+where
+    N: Display,
+    E: Display,
+    Ty: EdgeType,
+    W: Write,
+{
+    let mut names: HashMap<NodeID, String> = HashMap::new();
+    let mut seen: HashSet<String> = HashSet::new();
+    for (id, node) in graph.iter() {
+        let name = node.value.to_string();
+        if name.is_empty() || name.contains(|c: char| c.is_whitespace() || c == '#') {
+            return Err(EdgeListError::InvalidName { name });
+        }
+        if !seen.insert(name.clone()) {
+            return Err(EdgeListError::DuplicateName { name });
+        }
+        names.insert(*id, name);
+    }
+
+    let mut connected: HashSet<NodeID> = HashSet::new();
+    for (from_id, to_id, weight) in graph.edges() {
+        writeln!(writer, "{} {} {weight}", names[&from_id], names[&to_id])?;
+        connected.extend([from_id, to_id]);
+    }
+
+    for (id, _) in graph.iter() {
+        if !connected.contains(id) {
+            writeln!(writer, "{}", names[id])?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use utils_core::graph::{Directed, Graph, Node, UnGraph};
+
+    use super::{EdgeListError, read, write};
+
+    #[test]
+    fn test_edge_list_round_trip() {
+        let mut graph: Graph<&str> = Graph::new();
+        let home = graph.insert(Node::new("home"));
+        let station = graph.insert(Node::new("station"));
+        graph.insert(Node::new("nowhere"));
+        graph.connect(home, station, 3.0);
+        graph.connect(station, home, 2.5);
+
+        let mut output = Vec::new();
+        write(&graph, &mut output).unwrap();
+        let text = String::from_utf8(output).unwrap();
+        assert_eq!(text, "home station 3\nstation home 2.5\nnowhere\n");
+
+        let parsed: Graph<String> = read(text.as_bytes()).unwrap();
+        let values: Vec<&str> = parsed.iter().map(|(_, node)| node.value.as_str()).collect();
+        assert_eq!(values, ["home", "station", "nowhere"]);
+        assert_eq!(parsed.id("nowhere"), Some(2.into()));
+        assert_eq!(parsed.edge_weight(0.into(), 1.into()), Some(&3.0));
+        assert_eq!(parsed.edge_weight(1.into(), 0.into()), Some(&2.5));
+    }
+
+    #[test]
+    fn test_read_undirected_edge_list() {
+        let input = "# transit\na b\n\nb c 4 # express\n";
+        let graph: UnGraph<String> = read(input.as_bytes()).unwrap();
+        assert_eq!(graph.edge_weight(1.into(), 0.into()), Some(&1.0));
+        assert_eq!(graph.edge_weight(2.into(), 1.into()), Some(&4.0));
+    }
+
+    #[test]
+    fn test_edge_list_errors() {
+        let error = read::<_, Directed>("a b 1\nb c fast\n".as_bytes()).unwrap_err();
+        assert!(matches!(
+            error,
+            EdgeListError::InvalidWeight { line: 2, .. }
+        ));
+
+        for input in ["a b nan\n", "a b 1\nb c inf\n"] {
+            let error = read::<_, Directed>(input.as_bytes()).unwrap_err();
+            assert!(matches!(
+                error,
+                EdgeListError::InvalidWeight { line, .. } if line == input.lines().count()
+            ));
+        }
+
+        let error = read::<_, Directed>("a b 1\n\na b c d\n".as_bytes()).unwrap_err();
+        assert!(matches!(
+            error,
+            EdgeListError::FieldCount { count: 4, line: 3 }
+        ));
+
+        let mut graph: Graph<&str> = Graph::new();
+        graph.insert(Node::new("train station"));
+        assert!(matches!(
+            write(&graph, Vec::new()),
+            Err(EdgeListError::InvalidName { .. })
+        ));
+    }
+}
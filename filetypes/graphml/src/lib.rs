@@ -0,0 +1,540 @@
+//! GraphML, the XML based graph format.
+//!
+//! Node values are read from and written to a `label` key, connection weights
+//! to a `weight` key. Other keys are ignored, and nested graphs, hyperedges and
+//! ports are not supported.
+
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    io::{Error as IoError, Read, Write},
+};
+
+use quick_xml::{
+    Reader,
+    escape::escape,
+    events::{BytesStart, Event},
+};
+use thiserror::Error;
+use utils_core::graph::{EdgeType, Graph, Node, NodeID};
+
+#[derive(Error, Debug)]
+pub enum GraphmlError {
+    #[error("I/O error: {0}")]
+    Io(#[from] IoError),
+
+    #[error("Invalid XML at line {line}: {message}")]
+    Xml { message: String, line: usize },
+
+    #[error("Missing attribute `{attribute}` on <{element}> at line {line}")]
+    MissingAttribute {
+        element: &'static str,
+        attribute: &'static str,
+        line: usize,
+    },
+
+    #[error("Expected a {expected} graph at line {line}")]
+    Direction { expected: &'static str, line: usize },
+
+    #[error("Node `{id}` at line {line} is declared more than once")]
+    DuplicateNode { id: String, line: usize },
+
+    #[error("Unknown node `{id}` at line {line}")]
+    UnknownNode { id: String, line: usize },
+
+    #[error("Invalid weight `{value}` at line {line}")]
+    InvalidWeight { value: String, line: usize },
+
+    #[error("Unsupported GraphML feature at line {line}: {feature}")]
+    Unsupported { feature: &'static str, line: usize },
+
+    #[error("No <graph> element found")]
+    MissingGraph,
+}
+
+/// Reads the graph of a GraphML document.
+///
+/// Nodes are labeled with their `label` data, falling back to the key's default
+/// and then the node id, and can be looked up by their id with [`Graph::id`].
+/// Weights fall back to the key's default and then `1.0`, NaN and infinite
+/// weights are rejected. A connection marked `directed="false"` in a directed
+/// graph is added in both directions.
+pub fn read<R, Ty>(mut reader: R) -> Result<Graph<String, f64, Ty>, GraphmlError>
+where
+    R: Read,
+    Ty: EdgeType,
+{
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+    Parser::new(&input).parse()
+}
+
+/// Writes the graph as a GraphML document, labeling nodes with their value
+/// and connections with their weight.
+pub fn write<N, E, Ty, W>(graph: &Graph<N, E, Ty>, mut writer: W) -> Result<(), GraphmlError>
+where
+    N: Display,
+    E: Display,
+    Ty: EdgeType,
+    W: Write,
+{
+    let edge_default = if Ty::is_directed() {
+        "directed"
+    } else {
+        "undirected"
+    };
+
+    writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        writer,
+        r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns">"#
+    )?;
+    writeln!(
+        writer,
+        r#"  <key id="label" for="node" attr.name="label" attr.type="string"/>"#
+    )?;
+    writeln!(
+        writer,
+        r#"  <key id="weight" for="edge" attr.name="weight" attr.type="double"/>"#
+    )?;
+    writeln!(writer, r#"  <graph edgedefault="{edge_default}">"#)?;
+
+    let mut indices: HashMap<NodeID, usize> = HashMap::new();
+    for (index, (id, node)) in graph.iter().enumerate() {
+        indices.insert(*id, index);
+        writeln!(
+            writer,
+            r#"    <node id="n{index}"><data key="label">{}</data></node>"#,
+            escape(node.value.to_string())
+        )?;
+    }
+    for (from_id, to_id, weight) in graph.edges() {
+        writeln!(
+            writer,
+            r#"    <edge source="n{}" target="n{}"><data key="weight">{}</data></edge>"#,
+            indices[&from_id],
+            indices[&to_id],
+            escape(weight.to_string())
+        )?;
+    }
+
+    writeln!(writer, "  </graph>")?;
+    writeln!(writer, "</graphml>")?;
+    Ok(())
+}
+
+/// A connection that is added once all nodes are known
+struct PendingEdge {
+    source: String,
+    target: String,
+    weight: f64,
+    /// `false` for a `directed="false"` connection in a directed graph
+    directed: bool,
+    /// where the `<edge>` ends, turned into a line only for errors
+    position: u64,
+}
+
+/// The element the `<data>` currently being read belongs to
+enum Owner {
+    None,
+    Key(String),
+    Node(NodeID),
+    Edge(usize),
+}
+
+struct Parser<'a, Ty: EdgeType> {
+    input: &'a str,
+    reader: Reader<&'a [u8]>,
+    graph: Graph<String, f64, Ty>,
+    found_graph: bool,
+    edges: Vec<PendingEdge>,
+    label_key: Option<String>,
+    weight_key: Option<String>,
+    default_label: Option<String>,
+    default_weight: f64,
+    owner: Owner,
+    /// the key and text of the `<data>` or `<default>` currently being read
+    data: Option<(Option<String>, String)>,
+}
+
+impl<'a, Ty: EdgeType> Parser<'a, Ty> {
+    fn new(input: &'a str) -> Self {
+        let mut reader = Reader::from_str(input);
+        reader.config_mut().trim_text(true);
+
+        Self {
+            input,
+            reader,
+            graph: Graph::new(),
+            found_graph: false,
+            edges: Vec::new(),
+            label_key: None,
+            weight_key: None,
+            default_label: None,
+            default_weight: 1.0,
+            owner: Owner::None,
+            data: None,
+        }
+    }
+
+    /// The line of the end of the last event
+    fn line(&self) -> usize {
+        self.line_at(self.reader.buffer_position())
+    }
+
+    /// Counts the lines up to `position`, which takes time proportional to the
+    /// input, so this is only done for errors.
+    fn line_at(&self, position: u64) -> usize {
+        let end = (position as usize).min(self.input.len());
+        self.input.as_bytes()[..end]
+            .iter()
+            .filter(|&&byte| byte == b'\n')
+            .count()
+            + 1
+    }
+
+    fn xml_error(&self, error: impl Display) -> GraphmlError {
+        GraphmlError::Xml {
+            message: error.to_string(),
+            line: self.line(),
+        }
+    }
+
+    fn parse(mut self) -> Result<Graph<String, f64, Ty>, GraphmlError> {
+        loop {
+            let event = match self.reader.read_event() {
+                Ok(event) => event,
+                Err(error) => {
+                    return Err(GraphmlError::Xml {
+                        message: error.to_string(),
+                        line: self.line_at(self.reader.error_position()),
+                    });
+                }
+            };
+
+            match event {
+                Event::Start(element) => self.start(&element, false)?,
+                Event::Empty(element) => self.start(&element, true)?,
+                Event::End(element) => match element.local_name().as_ref() {
+                    b"key" | b"node" | b"edge" => self.owner = Owner::None,
+                    b"data" | b"default" => self.finish_data()?,
+                    _ => {}
+                },
+                Event::Text(text) => {
+                    let text = text.unescape().map_err(|error| self.xml_error(error))?;
+                    if let Some((_, content)) = &mut self.data {
+                        content.push_str(&text);
+                    }
+                }
+                Event::CData(text) => {
+                    if let Some((_, content)) = &mut self.data {
+                        content.push_str(&String::from_utf8_lossy(&text));
+                    }
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        if !self.found_graph {
+            return Err(GraphmlError::MissingGraph);
+        }
+
+        for edge in std::mem::take(&mut self.edges) {
+            let endpoint = |id: &String| {
+                self.graph.id(id).ok_or_else(|| GraphmlError::UnknownNode {
+                    id: id.clone(),
+                    line: self.line_at(edge.position),
+                })
+            };
+            let (from_id, to_id) = (endpoint(&edge.source)?, endpoint(&edge.target)?);
+
+            self.graph.connect(from_id, to_id, edge.weight);
+            if !edge.directed {
+                self.graph.connect(to_id, from_id, edge.weight);
+            }
+        }
+
+        Ok(self.graph)
+    }
+
+    fn start(&mut self, element: &BytesStart, empty: bool) -> Result<(), GraphmlError> {
+        let position = self.reader.buffer_position();
+        match element.local_name().as_ref() {
+            b"graph" => {
+                if self.found_graph {
+                    return Err(GraphmlError::Unsupported {
+                        feature: "more than one graph",
+                        line: self.line_at(position),
+                    });
+                }
+                self.found_graph = true;
+
+                let directed = match self.attribute(element, "edgedefault")?.as_deref() {
+                    Some("directed") => true,
+                    Some("undirected") => false,
+                    _ => {
+                        return Err(GraphmlError::MissingAttribute {
+                            element: "graph",
+                            attribute: "edgedefault",
+                            line: self.line_at(position),
+                        });
+                    }
+                };
+                if directed != Ty::is_directed() {
+                    return Err(GraphmlError::Direction {
+                        expected: if Ty::is_directed() {
+                            "directed"
+                        } else {
+                            "undirected"
+                        },
+                        line: self.line_at(position),
+                    });
+                }
+            }
+            b"key" => {
+                let id = self.required(element, "key", "id")?;
+                let domain = self.attribute(element, "for")?;
+                let name = self.attribute(element, "attr.name")?;
+                match (domain.as_deref(), name.as_deref()) {
+                    (Some("node" | "all"), Some("label")) => self.label_key = Some(id.clone()),
+                    (Some("edge" | "all"), Some("weight")) => self.weight_key = Some(id.clone()),
+                    _ => {}
+                }
+                if !empty {
+                    self.owner = Owner::Key(id);
+                }
+            }
+            b"node" => {
+                let id = self.required(element, "node", "id")?;
+                if self.graph.id(&id).is_some() {
+                    return Err(GraphmlError::DuplicateNode {
+                        id,
+                        line: self.line_at(position),
+                    });
+                }
+
+                let label = self.default_label.clone().unwrap_or_else(|| id.clone());
+                let node_id = self.graph.insert_named(id, Node::new(label));
+                if !empty {
+                    self.owner = Owner::Node(node_id);
+                }
+            }
+            b"edge" => {
+                let source = self.required(element, "edge", "source")?;
+                let target = self.required(element, "edge", "target")?;
+                let directed = match self.attribute(element, "directed")?.as_deref() {
+                    Some("false") => false,
+                    Some("true") if !Ty::is_directed() => {
+                        return Err(GraphmlError::Unsupported {
+                            feature: "directed connections in an undirected graph",
+                            line: self.line_at(position),
+                        });
+                    }
+                    _ => true,
+                };
+
+                self.edges.push(PendingEdge {
+                    source,
+                    target,
+                    weight: self.default_weight,
+                    // an undirected graph connects both ways anyway
+                    directed: directed || !Ty::is_directed(),
+                    position,
+                });
+                if !empty {
+                    self.owner = Owner::Edge(self.edges.len() - 1);
+                }
+            }
+            b"data" if !empty => {
+                let key = self.required(element, "data", "key")?;
+                self.data = Some((Some(key), String::new()));
+            }
+            b"default" if !empty => self.data = Some((None, String::new())),
+            b"hyperedge" => {
+                return Err(GraphmlError::Unsupported {
+                    feature: "hyperedges",
+                    line: self.line_at(position),
+                });
+            }
+            b"port" => {
+                return Err(GraphmlError::Unsupported {
+                    feature: "ports",
+                    line: self.line_at(position),
+                });
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Applies a finished `<data>` or `<default>` to its owner.
+    fn finish_data(&mut self) -> Result<(), GraphmlError> {
+        let Some((key, content)) = self.data.take() else {
+            return Ok(());
+        };
+        let position = self.reader.buffer_position();
+        let weight = |value: String| {
+            value
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .filter(|weight| weight.is_finite())
+                .ok_or_else(|| GraphmlError::InvalidWeight {
+                    line: self.line_at(position),
+                    value,
+                })
+        };
+
+        match (&self.owner, key) {
+            (Owner::Key(id), None) if Some(id) == self.label_key.as_ref() => {
+                self.default_label = Some(content);
+            }
+            (Owner::Key(id), None) if Some(id) == self.weight_key.as_ref() => {
+                self.default_weight = weight(content)?;
+            }
+            (&Owner::Node(node_id), Some(key)) if Some(&key) == self.label_key.as_ref() => {
+                if let Some(node) = self.graph.get_node_mut(&node_id) {
+                    node.value = content;
+                }
+            }
+            (&Owner::Edge(index), Some(key)) if Some(&key) == self.weight_key.as_ref() => {
+                self.edges[index].weight = weight(content)?;
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn attribute(
+        &self,
+        element: &BytesStart,
+        name: &'static str,
+    ) -> Result<Option<String>, GraphmlError> {
+        for attribute in element.attributes() {
+            let attribute = attribute.map_err(|error| self.xml_error(error))?;
+            if attribute.key.as_ref() == name.as_bytes() {
+                let value = attribute
+                    .unescape_value()
+                    .map_err(|error| self.xml_error(error))?;
+                return Ok(Some(value.into_owned()));
+            }
+        }
+        Ok(None)
+    }
+
+    fn required(
+        &self,
+        element: &BytesStart,
+        element_name: &'static str,
+        name: &'static str,
+    ) -> Result<String, GraphmlError> {
+        self.attribute(element, name)?
+            .ok_or_else(|| GraphmlError::MissingAttribute {
+                element: element_name,
+                attribute: name,
+                line: self.line(),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use utils_core::graph::{Directed, Graph, Node, UnGraph, Undirected};
+
+    use super::{GraphmlError, read, write};
+
+    #[test]
+    fn test_graphml_round_trip() {
+        let mut graph: Graph<&str> = Graph::new();
+        let home = graph.insert(Node::new("Home"));
+        let school = graph.insert(Node::new("School & Co"));
+        graph.insert(Node::new("Somewhere"));
+        graph.connect(home, school, 3.5);
+
+        let mut output = Vec::new();
+        write(&graph, &mut output).unwrap();
+        let text = String::from_utf8(output).unwrap();
+        assert!(text.contains(r#"<node id="n1"><data key="label">School &amp; Co</data></node>"#));
+
+        let parsed: Graph<String> = read(text.as_bytes()).unwrap();
+        let values: Vec<&str> = parsed.iter().map(|(_, node)| node.value.as_str()).collect();
+        assert_eq!(values, ["Home", "School & Co", "Somewhere"]);
+        assert_eq!(parsed.edge_weight(0.into(), 1.into()), Some(&3.5));
+        assert_eq!(parsed.edges().count(), 1);
+    }
+
+    #[test]
+    fn test_read_graphml_defaults() {
+        let input = r#"<?xml version="1.0"?>
+<graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+  <key id="d0" for="node" attr.name="label" attr.type="string"/>
+  <key id="d1" for="edge" attr.name="weight" attr.type="double">
+    <default>2.0</default>
+  </key>
+  <graph id="G" edgedefault="undirected">
+    <edge source="a" target="b"/>
+    <node id="a"><data key="d0">A</data></node>
+    <node id="b"/>
+    <edge source="b" target="a"><data key="d1">7</data></edge>
+  </graph>
+</graphml>"#;
+
+        let graph: UnGraph<String> = read(input.as_bytes()).unwrap();
+        let values: Vec<&str> = graph.iter().map(|(_, node)| node.value.as_str()).collect();
+        assert_eq!(values, ["A", "b"]);
+        assert_eq!(graph.id("a"), Some(0.into()));
+        let weights: Vec<f64> = graph.edges().map(|(_, _, weight)| *weight).collect();
+        assert_eq!(weights, [2.0, 7.0]);
+
+        assert!(matches!(
+            read::<_, Directed>(input.as_bytes()),
+            Err(GraphmlError::Direction { line: 7, .. })
+        ));
+    }
+
+    #[test]
+    fn test_graphml_errors() {
+        let unknown = r#"<graphml>
+  <graph edgedefault="directed">
+    <node id="a"/>
+    <edge source="a" target="b"/>
+  </graph>
+</graphml>"#;
+        assert!(matches!(
+            read::<_, Directed>(unknown.as_bytes()),
+            Err(GraphmlError::UnknownNode { line: 4, .. })
+        ));
+
+        let missing =
+            "<graphml>\n<graph edgedefault=\"undirected\">\n<node/>\n</graph>\n</graphml>";
+        assert!(matches!(
+            read::<_, Undirected>(missing.as_bytes()),
+            Err(GraphmlError::MissingAttribute {
+                element: "node",
+                line: 3,
+                ..
+            })
+        ));
+
+        let infinite = r#"<graphml>
+  <key id="w" for="edge" attr.name="weight"/>
+  <graph edgedefault="directed">
+    <node id="a"/>
+    <edge source="a" target="a"><data key="w">inf</data></edge>
+  </graph>
+</graphml>"#;
+        assert!(matches!(
+            read::<_, Directed>(infinite.as_bytes()),
+            Err(GraphmlError::InvalidWeight { line: 5, .. })
+        ));
+
+        let broken = "<graphml>\n<graph edgedefault=\"directed\">\n</graphml>";
+        assert!(matches!(
+            read::<_, Directed>(broken.as_bytes()),
+            Err(GraphmlError::Xml { line: 3, .. })
+        ));
+    }
+}
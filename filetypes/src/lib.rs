@@ -1 +1,2 @@
-
+pub use edge_list;
+pub use graphml;
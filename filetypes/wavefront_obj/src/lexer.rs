@@ -1,4 +1,4 @@
-use std::io::{BufRead, BufReader, Error as IoError, Read, Result as IoResult};
+use std::io::{BufRead, BufReader, Error as IoError, ErrorKind, Read, Result as IoResult};
 
 use thiserror::Error;
 
@@ -51,6 +51,13 @@ pub enum ParserError {
     #[error("I/O error: {0}")]
     Io(#[from] IoError),
 
+    #[error("Unexpected character {found:?} at physical line {line}, column {column}")]
+    UnexpectedChar {
+        found: char,
+        line: usize,
+        column: usize,
+    },
+
     #[error(
         "Unexpected token at physical line {line}, column {column}: expected {expected}, found {found:?}"
     )]
@@ -72,6 +79,10 @@ where
     physical_line_number: usize,
     column_number: usize,
     is_eof: bool,
+    /// whether the end of the current logical line was already returned
+    newline_returned: bool,
+    /// the next word is a file path, like after `mtllib`
+    expect_path: bool,
 }
 
 impl<R> Lexer<R>
@@ -86,11 +97,15 @@ where
             physical_line_number: 0,
             column_number: 0,
             is_eof: false,
+            newline_returned: false,
+            expect_path: false,
         }
     }
 
     fn read_next_logical_line(&mut self) -> IoResult<bool> {
         self.current_line.clear();
+        self.line_idx = 0;
+        self.column_number = 0;
 
         let mut temp_line = String::new();
         let mut read_any_line = false;
@@ -101,7 +116,7 @@ where
 
             if bytes_read == 0 {
                 self.is_eof = true;
-                return Ok(read_any_line)
+                return Ok(read_any_line);
             }
 
             self.physical_line_number += 1;
@@ -110,7 +125,8 @@ where
             let trimmed_temp_line = temp_line.trim_end_matches(['\n', '\r']);
             if trimmed_temp_line.ends_with('\\') {
                 // push up until the '\' character
-                self.current_line.push_str(&trimmed_temp_line[0..trimmed_temp_line.len() - 1]);
+                self.current_line
+                    .push_str(&trimmed_temp_line[0..trimmed_temp_line.len() - 1]);
             } else {
                 // push whole line
                 self.current_line.push_str(trimmed_temp_line);
@@ -122,17 +138,10 @@ where
     }
 
     fn peek_char(&self) -> Option<char> {
-        if self.is_eof {
-            return None;
-        }
         self.current_line.chars().nth(self.line_idx)
     }
 
     fn consume_char(&mut self) -> Option<char> {
-        if self.is_eof {
-            return None;
-        }
-
         let current_char_opt = self.current_line.chars().nth(self.line_idx);
         if current_char_opt.is_some() {
             self.line_idx += 1;
@@ -168,29 +177,147 @@ where
         Ok(s)
     }
 
-    fn next_token(&mut self) -> IoResult<ObjToken> {
+    /// Reads a file path, which runs up to the next whitespace.
+    fn read_path(&mut self) -> String {
+        let mut s = String::new();
+        while let Some(c) = self.peek_char() {
+            if c.is_whitespace() {
+                break;
+            }
+            s.push(c);
+            self.consume_char();
+        }
+        s
+    }
+
+    /// Reads an integer or a float like `-1.5e3`.
+    fn read_number(&mut self) -> IoResult<ObjToken> {
+        let mut s = String::new();
+        if let Some(sign @ ('-' | '+')) = self.peek_char() {
+            s.push(sign);
+            self.consume_char();
+        }
+        s.push_str(&self.read_digits()?);
+
+        let mut is_float = false;
+        if self.peek_char() == Some('.') {
+            is_float = true;
+            s.push('.');
+            self.consume_char();
+            s.push_str(&self.read_digits()?);
+        }
+        if let Some(e @ ('e' | 'E')) = self.peek_char() {
+            is_float = true;
+            s.push(e);
+            self.consume_char();
+            if let Some(sign @ ('-' | '+')) = self.peek_char() {
+                s.push(sign);
+                self.consume_char();
+            }
+            s.push_str(&self.read_digits()?);
+        }
+
+        let invalid = || IoError::new(ErrorKind::InvalidData, format!("invalid number `{s}`"));
+        if !is_float && let Ok(value) = s.parse() {
+            return Ok(ObjToken::Integer(value));
+        }
+        // integers that don't fit an i32 are still valid floats
+        s.parse().map(ObjToken::Float).map_err(|_| invalid())
+    }
+
+    /// Reads the next token, with a [`ObjToken::Newline`] at the end of every
+    /// logical line and [`ObjToken::Eof`] once the input is exhausted.
+    pub fn next_token(&mut self) -> Result<ObjToken, ParserError> {
         loop {
-            if self.peek_char().is_none() && !self.is_eof {
-                if !self.read_next_logical_line()? {
+            let Some(current_char) = self.peek_char() else {
+                if self.physical_line_number > 0 && !self.newline_returned {
+                    self.newline_returned = true;
+                    self.expect_path = false;
+                    return Ok(ObjToken::Newline);
+                }
+                if self.is_eof || !self.read_next_logical_line()? {
                     return Ok(ObjToken::Eof);
                 }
+                self.newline_returned = false;
+                continue;
+            };
+
+            if current_char.is_whitespace() {
+                while self.peek_char().is_some_and(char::is_whitespace) {
+                    self.consume_char();
+                }
+                return Ok(ObjToken::Whitespace);
             }
 
-            let current_char = if let Some(c) = self.peek_char() {
-                c
-            } else {
-                return Ok(ObjToken::Eof);
+            let single = match current_char {
+                '/' => Some(ObjToken::Slash),
+                '#' => Some(ObjToken::Hash),
+                '\\' => Some(ObjToken::Backslash),
+                _ => None,
             };
+            if let Some(token) = single {
+                self.consume_char();
+                return Ok(token);
+            }
+
+            if self.expect_path {
+                self.expect_path = false;
+                return Ok(ObjToken::Path(self.read_path()));
+            }
+
+            let next_char = self.current_line.chars().nth(self.line_idx + 1);
+            let starts_number = current_char.is_ascii_digit()
+                || (matches!(current_char, '-' | '+' | '.')
+                    && next_char.is_some_and(|c| c.is_ascii_digit() || c == '.'));
+            if starts_number {
+                return Ok(self.read_number()?);
+            }
 
+            if current_char.is_ascii_alphanumeric() || current_char == '_' {
+                let identifier = self.read_identifier()?;
+                let token = match identifier.as_str() {
+                    "v" => ObjToken::V,
+                    "vt" => ObjToken::Vt,
+                    "vn" => ObjToken::Vn,
+                    "f" => ObjToken::F,
+                    "o" => ObjToken::O,
+                    "g" => ObjToken::G,
+                    "s" => ObjToken::S,
+                    "mtllib" => {
+                        self.expect_path = true;
+                        ObjToken::Mtllib
+                    }
+                    "usemtl" => ObjToken::Usemtl,
+                    _ => ObjToken::Identifier(identifier),
+                };
+                return Ok(token);
+            }
 
-            todo!();
+            return Err(ParserError::UnexpectedChar {
+                found: current_char,
+                line: self.physical_line_number,
+                column: self.column_number + 1,
+            });
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Lexer;
+    use super::{Lexer, ObjToken, ParserError};
+
+    /// Every token up to the end of the input, without whitespace
+    fn tokens(content: &str) -> Vec<ObjToken> {
+        let mut lexer = Lexer::new(content.as_bytes());
+        let mut tokens = Vec::new();
+        loop {
+            match lexer.next_token().unwrap() {
+                ObjToken::Eof => return tokens,
+                ObjToken::Whitespace => {}
+                token => tokens.push(token),
+            }
+        }
+    }
 
     #[test]
     fn test_lex_vertex() {
@@ -198,6 +325,60 @@ mod tests {
             v 5.0 5.0 5.0
         "#;
 
-        let lexer = Lexer::new(obj_content.as_bytes());
+        assert_eq!(
+            tokens(obj_content),
+            [
+                ObjToken::Newline,
+                ObjToken::V,
+                ObjToken::Float(5.0),
+                ObjToken::Float(5.0),
+                ObjToken::Float(5.0),
+                ObjToken::Newline,
+                ObjToken::Newline,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lex_faces_and_materials() {
+        let obj_content = "mtllib materials/cube.mtl\nusemtl red_1\nf 1/2/3 \\\n4//-6 # back\n";
+
+        assert_eq!(
+            tokens(obj_content),
+            [
+                ObjToken::Mtllib,
+                ObjToken::Path("materials/cube.mtl".to_string()),
+                ObjToken::Newline,
+                ObjToken::Usemtl,
+                ObjToken::Identifier("red_1".to_string()),
+                ObjToken::Newline,
+                ObjToken::F,
+                ObjToken::Integer(1),
+                ObjToken::Slash,
+                ObjToken::Integer(2),
+                ObjToken::Slash,
+                ObjToken::Integer(3),
+                ObjToken::Integer(4),
+                ObjToken::Slash,
+                ObjToken::Slash,
+                ObjToken::Integer(-6),
+                ObjToken::Hash,
+                ObjToken::Identifier("back".to_string()),
+                ObjToken::Newline,
+            ]
+        );
+
+        let mut lexer = Lexer::new("v 1\nv ?".as_bytes());
+        let error = std::iter::from_fn(|| Some(lexer.next_token()))
+            .find_map(Result::err)
+            .unwrap();
+        assert!(matches!(
+            error,
+            ParserError::UnexpectedChar {
+                found: '?',
+                line: 2,
+                column: 3
+            }
+        ));
     }
 }
@@ -1 +1,3 @@
 mod lexer;
+
+pub use lexer::{Lexer, ObjToken, ParserError};
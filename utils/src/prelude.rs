@@ -1,3 +1,2 @@
-pub use utils_core::graph::{Graph, Node, NodeID};
+pub use utils_core::graph::{DiGraph, Graph, GraphBuilder, Node, NodeID, UnGraph};
 pub use utils_macro::{self, *};
-
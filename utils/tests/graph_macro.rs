@@ -0,0 +1,300 @@
+use utils::prelude::*;
+
+#[test]
+fn test_graph_macro_custom_edges() {
+    #[derive(Debug, Clone, PartialEq)]
+    enum Mode {
+        Walk,
+        Train,
+    }
+
+    let graph: Graph<&str, Mode> = graph! {
+        nodes: {
+            home = "Home";
+            station = "Station";
+            school = "School";
+        }
+        connections: {
+            home <-> station: Mode::Walk;
+            station -> school: Mode::Train;
+        }
+    };
+
+    let home = NodeID::from(0);
+    let station = NodeID::from(1);
+    let school = NodeID::from(2);
+    assert_eq!(graph.out_degree(home), 1);
+    assert_eq!(graph.out_degree(station), 2);
+    assert_eq!(graph.in_degree(school), 1);
+    assert_eq!(
+        graph.shortest_path_by(home, school, |_| 1.0),
+        Some((2.0, vec![home, station, school]))
+    );
+}
+
+#[test]
+fn test_graph_macro_unweighted_edges() {
+    let graph: Graph<i32, u32> = graph! {
+        nodes: {
+            a = 1;
+            b = 2;
+        }
+        connections: {
+            a -> b;
+        }
+    };
+
+    assert_eq!(
+        graph
+            .shortest_path(NodeID::from(0), NodeID::from(1))
+            .map(|(cost, _)| cost),
+        Some(1.0)
+    );
+}
+
+#[test]
+fn test_graph_macro_undirected_graph() {
+    let graph: UnGraph<&str> = graph! {
+        nodes: {
+            a = "a";
+            b = "b";
+            c = "c";
+        }
+        connections: {
+            a <-> b: 2.0;
+            b -> c: 1.0;
+        }
+    };
+
+    let a = NodeID::from(0);
+    let c = NodeID::from(2);
+    assert_eq!(graph.edges().count(), 2);
+    assert_eq!(graph.shortest_path(c, a).map(|(cost, _)| cost), Some(3.0));
+}
+
+#[test]
+fn test_graph_macro_keeps_node_names() {
+    let graph: Graph<&str> = graph! {
+        nodes: {
+            home = "Home";
+            station = "Station";
+            school = "School";
+        }
+        connections: {
+            home -> station: 3.0;
+            station -> school: 18.0;
+        }
+    };
+
+    let home = graph.id("home").unwrap();
+    let school = graph.id("school").unwrap();
+    assert_eq!(graph.get_node(&home).map(|node| node.value), Some("Home"));
+    assert_eq!(
+        graph.shortest_path(home, school).map(|(cost, _)| cost),
+        Some(21.0)
+    );
+    assert_eq!(graph.id("Home"), None);
+
+    let simple: Graph<i32> = graph!(a = 1, b = 2);
+    assert_eq!(simple.id("b"), Some(NodeID::from(1)));
+}
+
+#[test]
+fn test_graph_macro_moves_node_values() {
+    let home = String::from("Home");
+    let parse = |value: &str| value.parse::<u8>();
+
+    let build = || -> Result<Graph<String, u8>, std::num::ParseIntError> {
+        Ok(graph! {
+            nodes: {
+                home = home;
+                school = "School".to_string();
+            }
+            connections: {
+                home -> school: parse("12")?;
+            }
+        })
+    };
+
+    let graph = build().unwrap();
+    let values: Vec<&str> = graph.iter().map(|(_, node)| node.value.as_str()).collect();
+    assert_eq!(values, ["Home", "School"]);
+    assert_eq!(graph.edges().count(), 1);
+}
+
+#[test]
+fn test_graph_macro_weight_expressions() {
+    const DIST: f64 = 1.5;
+    let scale = |distance: f64| distance * 10.0;
+    let detour = 4.0;
+
+    let graph: Graph<&str> = graph! {
+        nodes: {
+            a = "a";
+            b = "b";
+            c = "c";
+        }
+        connections: {
+            a -> b: 3;
+            b -> c: DIST * 2.0;
+            a -> c: scale(detour);
+            c -> a: -2;
+        }
+    };
+
+    let (a, b, c) = (NodeID::from(0), NodeID::from(1), NodeID::from(2));
+    assert_eq!(graph.edge_weight(a, b), Some(&3.0));
+    assert_eq!(graph.edge_weight(b, c), Some(&3.0));
+    assert_eq!(graph.edge_weight(a, c), Some(&40.0));
+    assert_eq!(graph.edge_weight(c, a), Some(&-2.0));
+
+    let counted: Graph<&str, u64> = graph! {
+        nodes: {
+            a = "a";
+            b = "b";
+        }
+        connections: {
+            a <-> b: 70000;
+        }
+    };
+    assert_eq!(counted.edge_weight(b, a), Some(&70000));
+
+    let sized: Graph<&str, usize> = graph!(a = "a", b = "b", a -> b: 70000, b -> a: 3);
+    assert_eq!(sized.edge_weight(a, b), Some(&70000));
+    assert_eq!(sized.edge_weight(b, a), Some(&3));
+
+    let single: Graph<&str, f32> = graph!(a = "a", b = "b", a -> b: 70000, b -> a: -3);
+    assert_eq!(single.edge_weight(a, b), Some(&70000.0));
+    assert_eq!(single.edge_weight(b, a), Some(&-3.0));
+}
+
+#[test]
+fn test_graph_macro_weights_see_caller_variables() {
+    let from_id = 1.0;
+    let to_id = 2.0;
+    let weight = 3.0;
+    let graph = 4.0;
+
+    let built: Graph<&str> = graph! {
+        nodes: {
+            a = "a";
+            b = "b";
+            c = "c";
+        }
+        connections: {
+            a -> b: from_id;
+            b -> a: to_id;
+            {a, b} -> c: weight + graph;
+        }
+    };
+
+    let (a, b) = (built.id("a").unwrap(), built.id("b").unwrap());
+    assert_eq!(built.edge_weight(b, a), Some(&2.0));
+    let weights: Vec<f64> = built.edges().map(|(_, _, weight)| *weight).collect();
+    assert_eq!(weights, [1.0, 2.0, 7.0, 7.0]);
+}
+
+#[test]
+fn test_graph_macro_chains_and_groups() {
+    let evaluated = std::cell::Cell::new(0);
+    let weight = |value: f64| {
+        evaluated.set(evaluated.get() + 1);
+        value
+    };
+
+    let graph: Graph<&str> = graph! {
+        nodes: {
+            source = "source";
+            parse = "parse";
+            check = "check";
+            lint = "lint";
+            build = "build";
+            test = "test";
+        }
+        connections: {
+            source -> parse -> {check, lint}: weight(1.0);
+            {check, lint} -> build <-> test;
+        }
+    };
+
+    let id = |name| graph.id(name).unwrap();
+    assert_eq!(evaluated.get(), 1);
+    assert_eq!(graph.edges().count(), 7);
+    assert_eq!(graph.edge_weight(id("source"), id("parse")), Some(&1.0));
+    assert_eq!(graph.edge_weight(id("parse"), id("lint")), Some(&1.0));
+    assert!(graph.contains_edge(id("check"), id("build")));
+    assert!(graph.contains_edge(id("lint"), id("build")));
+    assert!(graph.contains_edge(id("test"), id("build")));
+    assert_eq!(graph.out_degree(id("parse")), 2);
+}
+
+#[test]
+fn test_graph_macro_simple_form_connections() {
+    let graph: Graph<i32> = graph!(a = 1, b = 2, a -> b: 2.5, c = 3, b <-> c);
+
+    let (a, b, c) = (
+        graph.id("a").unwrap(),
+        graph.id("b").unwrap(),
+        graph.id("c").unwrap(),
+    );
+    assert_eq!(graph.iter().count(), 3);
+    assert_eq!(graph.edge_weight(a, b), Some(&2.5));
+    assert_eq!(graph.edge_weight(c, b), Some(&1.0));
+}
+
+#[test]
+fn test_graph_macro_implicit_nodes() {
+    let graph: Graph<String> = graph! {
+        #![implicit_nodes]
+        nodes: {
+            start = "Start".to_string();
+        }
+        connections: {
+            start -> fetch -> {unpack, verify};
+        }
+    };
+
+    let values: Vec<&str> = graph.iter().map(|(_, node)| node.value.as_str()).collect();
+    assert_eq!(values, ["Start", "", "", ""]);
+    assert_eq!(graph.id("verify"), Some(NodeID::from(3)));
+    assert_eq!(graph.edges().count(), 3);
+
+    let pipeline: UnGraph<u8> = graph! {
+        #![implicit_nodes]
+        connections: {
+            a <-> b;
+        }
+    };
+    assert_eq!(pipeline.edges().count(), 1);
+
+    let simple: Graph<u8> = graph!(#![implicit_nodes] x = 7, x -> y -> z);
+    assert_eq!(
+        simple.get_node(&NodeID::from(0)).map(|node| node.value),
+        Some(7)
+    );
+    assert_eq!(simple.edges().count(), 2);
+}
+
+#[test]
+#[allow(deprecated)] // the lints of `graph!` are deprecation warnings
+fn test_graph_macro_lints_keep_connections() {
+    let graph: Graph<u8> = graph! {
+        nodes: {
+            a = 1;
+            b = 2;
+            unused = 3;
+        }
+        connections: {
+            a -> b: 1.0;
+            a <-> b: 2.0;
+            b -> b: 3.0;
+        }
+    };
+
+    let (a, b) = (graph.id("a").unwrap(), graph.id("b").unwrap());
+    assert_eq!(graph.iter().count(), 3);
+    // repeated connections are still inserted as parallel ones
+    assert_eq!(graph.edges().count(), 4);
+    assert_eq!(graph.edge_weight(b, a), Some(&2.0));
+    assert_eq!(graph.edge_weight(b, b), Some(&3.0));
+}
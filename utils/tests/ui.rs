@@ -0,0 +1,5 @@
+#[test]
+fn test_graph_macro_errors() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,21 @@
+#![deny(deprecated)]
+
+use utils::prelude::*;
+
+fn main() {
+    let _graph: Graph<&str> = graph! {
+        nodes: {
+            home = "Home";
+            school = "School";
+            somewhere_else = "Somewhere";
+            nowhere = "Nowhere";
+        }
+        connections: {
+            home <-> school: 3.0;
+            school -> home: 3.0;
+            home -> somewhere_else: 5.0;
+            home -> somewhere_else: 5.0;
+            school -> school: 1.0;
+        }
+    };
+}
@@ -0,0 +1,17 @@
+use utils::prelude::*;
+
+fn main() {
+    let _graph: Graph<&str> = graph! {
+        nodes: {
+            home = "Home";
+            train_station_near_home = "Train station near Home";
+            school = "School";
+            home = "Home again";
+        }
+        connections: {
+            home <-> train_staton_near_home: 3.0;
+            train_station_near_home -> shcool: 18.0;
+            school -> somewhere_else: 5.0;
+        }
+    };
+}
@@ -0,0 +1,17 @@
+use utils::prelude::*;
+
+fn main() {
+    let _graph: Graph<&str> = graph! {
+        #![strict]
+        #![check_dead_ends]
+        nodes: {
+            home = "Home";
+            school = "School";
+            somewhere_else = "Somewhere";
+        }
+        connections: {
+            home <-> school: 3.0;
+            home -> somewhere_else: 5.0;
+        }
+    };
+}
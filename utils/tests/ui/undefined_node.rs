@@ -0,0 +1,19 @@
+use utils::prelude::*;
+
+fn main() {
+    let _simple: Graph<i32> = graph!(a = 1, a -> b);
+
+    let _structured: Graph<i32> = graph! {
+        nodes: {
+            a = 1;
+        }
+        connections: {
+            a -> {a, b};
+        }
+    };
+
+    let _option: Graph<i32> = graph! {
+        #![implicit]
+        a = 1
+    };
+}
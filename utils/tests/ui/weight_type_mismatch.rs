@@ -0,0 +1,13 @@
+use utils::prelude::*;
+
+fn main() {
+    let _graph: Graph<&str> = graph! {
+        nodes: {
+            a = "a";
+            b = "b";
+        }
+        connections: {
+            a -> b: "far";
+        }
+    };
+}
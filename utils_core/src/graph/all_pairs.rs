@@ -0,0 +1,258 @@
+use std::collections::HashMap;
+
+use super::{EdgeType, EdgeWeight, Graph, NegativeCycle, NodeID};
+
+/// The algorithm used by [`Graph::all_pairs_shortest_paths`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AllPairsAlgorithm {
+    /// O(n³), the better choice for dense graphs.
+    #[default]
+    FloydWarshall,
+    /// Bellman-Ford once and Dijkstra from every node, O(n·m·log n),
+    /// the better choice for sparse graphs.
+    Johnson,
+}
+
+/// The cheapest paths between every pair of nodes, indexed by `NodeID`.
+#[derive(Debug, Clone)]
+pub struct DistanceMatrix {
+    nodes: Vec<NodeID>,
+    index: HashMap<NodeID, usize>,
+    /// row-major, `f64::INFINITY` where there is no path
+    distances: Vec<f64>,
+    /// the node before the column node on the path from the row node
+    predecessors: Vec<Option<usize>>,
+}
+
+impl DistanceMatrix {
+    fn new(nodes: Vec<NodeID>) -> Self {
+        let len = nodes.len();
+        let index = nodes.iter().enumerate().map(|(i, &id)| (id, i)).collect();
+
+        let mut distances = vec![f64::INFINITY; len * len];
+        for i in 0..len {
+            distances[i * len + i] = 0.0;
+        }
+
+        Self {
+            nodes,
+            index,
+            distances,
+            predecessors: vec![None; len * len],
+        }
+    }
+
+    fn cell(&self, from: usize, to: usize) -> usize {
+        from * self.nodes.len() + to
+    }
+
+    /// The nodes of the matrix in `NodeID` order.
+    pub fn nodes(&self) -> &[NodeID] {
+        &self.nodes
+    }
+
+    /// The cost of the cheapest path from `from_id` to `to_id`, or `None` if there is none.
+    pub fn distance(&self, from_id: NodeID, to_id: NodeID) -> Option<f64> {
+        let cell = self.cell(*self.index.get(&from_id)?, *self.index.get(&to_id)?);
+        Some(self.distances[cell]).filter(|distance| distance.is_finite())
+    }
+
+    /// The cheapest path from `from_id` to `to_id`, starting with `from_id`.
+    pub fn path(&self, from_id: NodeID, to_id: NodeID) -> Option<Vec<NodeID>> {
+        self.distance(from_id, to_id)?;
+        let (from, mut current) = (self.index[&from_id], self.index[&to_id]);
+
+        let mut path = vec![to_id];
+        while current != from {
+            current = self.predecessors[self.cell(from, current)]?;
+            path.push(self.nodes[current]);
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+impl<N, E, Ty> Graph<N, E, Ty>
+where
+    E: EdgeWeight,
+    Ty: EdgeType,
+{
+    /// Finds the cheapest path between every pair of nodes.
+    ///
+    /// Negative weights are allowed, but fail with a [`NegativeCycle`] if they form one.
+    pub fn all_pairs_shortest_paths(
+        &self,
+        algorithm: AllPairsAlgorithm,
+    ) -> Result<DistanceMatrix, NegativeCycle> {
+        self.all_pairs_shortest_paths_by(algorithm, E::weight)
+    }
+}
+
+impl<N, E, Ty> Graph<N, E, Ty>
+where
+    Ty: EdgeType,
+{
+    /// Like [`Graph::all_pairs_shortest_paths`], but takes the cost of a connection from `edge_cost`.
+    pub fn all_pairs_shortest_paths_by(
+        &self,
+        algorithm: AllPairsAlgorithm,
+        edge_cost: impl Fn(&E) -> f64,
+    ) -> Result<DistanceMatrix, NegativeCycle> {
+        match algorithm {
+            AllPairsAlgorithm::FloydWarshall => self.floyd_warshall(&edge_cost),
+            AllPairsAlgorithm::Johnson => self.johnson(&edge_cost),
+        }
+    }
+
+    fn floyd_warshall(
+        &self,
+        edge_cost: &impl Fn(&E) -> f64,
+    ) -> Result<DistanceMatrix, NegativeCycle> {
+        let mut matrix = DistanceMatrix::new(self.nodes.keys().copied().collect());
+        let len = matrix.nodes.len();
+
+        for (from_id, to_id, weight) in self.edges() {
+            let (from, to) = (matrix.index[&from_id], matrix.index[&to_id]);
+            // undirected connections can be walked both ways
+            let directions = if Ty::is_directed() {
+                &[(from, to)][..]
+            } else {
+                &[(from, to), (to, from)][..]
+            };
+
+            let cost = edge_cost(weight);
+            for &(from, to) in directions {
+                let cell = matrix.cell(from, to);
+                if cost < matrix.distances[cell] {
+                    matrix.distances[cell] = cost;
+                    matrix.predecessors[cell] = Some(from);
+                }
+            }
+        }
+
+        for via in 0..len {
+            for from in 0..len {
+                let first = matrix.distances[matrix.cell(from, via)];
+                if first == f64::INFINITY {
+                    continue;
+                }
+
+                for to in 0..len {
+                    let cost = first + matrix.distances[matrix.cell(via, to)];
+                    let cell = matrix.cell(from, to);
+                    if cost < matrix.distances[cell] {
+                        matrix.distances[cell] = cost;
+                        matrix.predecessors[cell] = matrix.predecessors[matrix.cell(via, to)];
+                    }
+                }
+            }
+        }
+
+        // a node that can reach itself for less than nothing lies on a negative
+        // cycle, unless rounding left a cycle that costs nothing slightly below
+        // zero, so the same check as Johnson's has the final say
+        let diagonal: Vec<usize> = (0..len).map(|i| matrix.cell(i, i)).collect();
+        if diagonal.iter().any(|&cell| matrix.distances[cell] < 0.0) {
+            self.relax_from(&matrix.nodes, edge_cost)?;
+            for cell in diagonal {
+                matrix.distances[cell] = 0.0;
+            }
+        }
+
+        Ok(matrix)
+    }
+
+    fn johnson(&self, edge_cost: &impl Fn(&E) -> f64) -> Result<DistanceMatrix, NegativeCycle> {
+        let nodes: Vec<NodeID> = self.nodes.keys().copied().collect();
+
+        // reweighting with these potentials makes every connection non-negative
+        let (potentials, _) = self.relax_from(&nodes, edge_cost)?;
+        let reweighted = |from_id: NodeID, to_id: NodeID, weight: &E| {
+            edge_cost(weight) + potentials[&from_id] - potentials[&to_id]
+        };
+
+        let mut matrix = DistanceMatrix::new(nodes);
+        for from in 0..matrix.nodes.len() {
+            let from_id = matrix.nodes[from];
+            let (distances, predecessors) = self.dijkstra_from_by(from_id, &reweighted);
+            for (to_id, distance) in distances {
+                let cell = matrix.cell(from, matrix.index[&to_id]);
+                matrix.distances[cell] = distance - potentials[&from_id] + potentials[&to_id];
+                matrix.predecessors[cell] = predecessors.get(&to_id).map(|id| matrix.index[id]);
+            }
+        }
+
+        Ok(matrix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AllPairsAlgorithm;
+    use crate::graph::{Graph, Node, UnGraph};
+
+    #[test]
+    fn test_all_pairs_algorithms_agree() {
+        let mut graph: Graph<&str> = Graph::new();
+        let a = graph.insert(Node::new("a"));
+        let b = graph.insert(Node::new("b"));
+        let c = graph.insert(Node::new("c"));
+        let d = graph.insert(Node::new("d"));
+
+        graph.connect(a, b, 3.0);
+        graph.connect(a, c, 8.0);
+        graph.connect(b, c, -2.0);
+        graph.connect(c, a, 4.0);
+        graph.connect(c, d, 1.0);
+
+        for algorithm in [AllPairsAlgorithm::FloydWarshall, AllPairsAlgorithm::Johnson] {
+            let matrix = graph.all_pairs_shortest_paths(algorithm).unwrap();
+            assert_eq!(matrix.distance(a, c), Some(1.0));
+            assert_eq!(matrix.distance(c, b), Some(7.0));
+            assert_eq!(matrix.distance(d, a), None);
+            assert_eq!(matrix.path(a, d), Some(vec![a, b, c, d]));
+            assert_eq!(matrix.path(b, a), Some(vec![b, c, a]));
+            assert_eq!(matrix.path(b, b), Some(vec![b]));
+        }
+
+        graph.connect(d, a, -3.0);
+        for algorithm in [AllPairsAlgorithm::FloydWarshall, AllPairsAlgorithm::Johnson] {
+            assert!(graph.all_pairs_shortest_paths(algorithm).is_err());
+        }
+    }
+
+    #[test]
+    fn test_all_pairs_zero_cost_cycle() {
+        // adds up to zero, but not when summed in every order
+        let mut graph: Graph<&str> = Graph::new();
+        let a = graph.insert(Node::new("a"));
+        let b = graph.insert(Node::new("b"));
+        let c = graph.insert(Node::new("c"));
+        graph.connect(a, b, 0.3);
+        graph.connect(b, c, -0.1);
+        graph.connect(c, a, -0.2);
+
+        for algorithm in [AllPairsAlgorithm::FloydWarshall, AllPairsAlgorithm::Johnson] {
+            let matrix = graph.all_pairs_shortest_paths(algorithm).unwrap();
+            assert_eq!(matrix.distance(a, a), Some(0.0));
+            assert_eq!(matrix.path(a, c), Some(vec![a, b, c]));
+        }
+    }
+
+    #[test]
+    fn test_all_pairs_undirected() {
+        let mut graph: UnGraph<&str> = UnGraph::new();
+        let a = graph.insert(Node::new("a"));
+        let b = graph.insert(Node::new("b"));
+        let c = graph.insert(Node::new("c"));
+
+        graph.connect(a, b, 2.0);
+        graph.connect(c, b, 5.0);
+
+        for algorithm in [AllPairsAlgorithm::FloydWarshall, AllPairsAlgorithm::Johnson] {
+            let matrix = graph.all_pairs_shortest_paths(algorithm).unwrap();
+            assert_eq!(matrix.distance(c, a), Some(7.0));
+            assert_eq!(matrix.path(c, a), Some(vec![c, b, a]));
+        }
+    }
+}
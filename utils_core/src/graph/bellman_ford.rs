@@ -0,0 +1,216 @@
+use std::collections::HashMap;
+
+use thiserror::Error;
+
+use super::{EdgeType, EdgeWeight, Graph, NodeID, shortest_path::SearchTree};
+
+/// Returned when a shortest path search runs into a cycle whose weights add up to
+/// less than zero, so walking it again and again makes every path cheaper.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Graph contains a negative cycle: {nodes:?}")]
+pub struct NegativeCycle {
+    /// the nodes of the cycle in walking order, the last one connects back to the first
+    pub nodes: Vec<NodeID>,
+}
+
+/// The cheapest paths from one source node to every node it can reach.
+#[derive(Debug, Clone)]
+pub struct ShortestPaths {
+    source: NodeID,
+    distances: HashMap<NodeID, f64>,
+    predecessors: HashMap<NodeID, NodeID>,
+}
+
+impl ShortestPaths {
+    /// The node every path starts at.
+    pub fn source(&self) -> NodeID {
+        self.source
+    }
+
+    /// The cost of the cheapest path to `id`, or `None` if it can't be reached.
+    pub fn distance(&self, id: NodeID) -> Option<f64> {
+        self.distances.get(&id).copied()
+    }
+
+    /// The cheapest path from the source to `id`, starting with the source.
+    pub fn path_to(&self, id: NodeID) -> Option<Vec<NodeID>> {
+        self.distances.get(&id)?;
+
+        let mut path = vec![id];
+        let mut current = id;
+        while current != self.source {
+            current = self.predecessors[&current];
+            path.push(current);
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+impl<N, E, Ty> Graph<N, E, Ty>
+where
+    E: EdgeWeight,
+    Ty: EdgeType,
+{
+    /// Finds the cheapest paths from `source` to every other node using the
+    /// Bellman-Ford algorithm, which unlike [`Graph::shortest_path`] handles
+    /// negative weights.
+    ///
+    /// Fails if a negative cycle can be reached from `source`. In an undirected
+    /// graph every negative connection is such a cycle.
+    pub fn bellman_ford(&self, source: NodeID) -> Result<ShortestPaths, NegativeCycle> {
+        self.bellman_ford_by(source, E::weight)
+    }
+}
+
+impl<N, E, Ty> Graph<N, E, Ty>
+where
+    Ty: EdgeType,
+{
+    /// Like [`Graph::bellman_ford`], but takes the cost of a connection from `edge_cost`.
+    pub fn bellman_ford_by(
+        &self,
+        source: NodeID,
+        edge_cost: impl Fn(&E) -> f64,
+    ) -> Result<ShortestPaths, NegativeCycle> {
+        let sources: &[NodeID] = if self.nodes.contains_key(&source) {
+            &[source]
+        } else {
+            &[]
+        };
+        let (distances, predecessors) = self.relax_from(sources, &edge_cost)?;
+
+        Ok(ShortestPaths {
+            source,
+            distances,
+            predecessors,
+        })
+    }
+
+    /// The core of Bellman-Ford: every node in `sources` starts at distance zero,
+    /// as if they were all connected to one virtual node, and connections are
+    /// relaxed until nothing changes anymore.
+    pub(super) fn relax_from(
+        &self,
+        sources: &[NodeID],
+        edge_cost: &impl Fn(&E) -> f64,
+    ) -> Result<SearchTree, NegativeCycle> {
+        let mut distances: HashMap<NodeID, f64> = sources.iter().map(|&id| (id, 0.0)).collect();
+        let mut predecessors: HashMap<NodeID, NodeID> = HashMap::new();
+
+        // after `n` rounds every path without a cycle has been found,
+        // counting the virtual node that leads to the sources
+        for _ in 0..self.nodes.len() {
+            let mut changed = false;
+            for &id in self.nodes.keys() {
+                let Some(&cost) = distances.get(&id) else {
+                    continue;
+                };
+
+                for (_, next, weight) in self.outgoing(id) {
+                    let next_cost = cost + edge_cost(weight);
+                    if distances
+                        .get(&next)
+                        .is_none_or(|&current| next_cost < current)
+                    {
+                        distances.insert(next, next_cost);
+                        predecessors.insert(next, id);
+                        changed = true;
+                    }
+                }
+            }
+
+            if !changed {
+                return Ok((distances, predecessors));
+            }
+        }
+
+        // anything that can still get cheaper lies on or behind a negative cycle
+        for &id in self.nodes.keys() {
+            let Some(&cost) = distances.get(&id) else {
+                continue;
+            };
+
+            for (_, next, weight) in self.outgoing(id) {
+                if cost + edge_cost(weight) < distances[&next] {
+                    predecessors.insert(next, id);
+                    return Err(self.negative_cycle(&predecessors, next));
+                }
+            }
+        }
+
+        Ok((distances, predecessors))
+    }
+
+    /// Follows the predecessors from `start` until it is certainly inside the cycle
+    /// and collects the cycle from there.
+    fn negative_cycle(
+        &self,
+        predecessors: &HashMap<NodeID, NodeID>,
+        start: NodeID,
+    ) -> NegativeCycle {
+        let mut current = start;
+        for _ in 0..self.nodes.len() {
+            current = predecessors[&current];
+        }
+
+        let mut nodes = vec![current];
+        let mut member = predecessors[&current];
+        while member != current {
+            nodes.push(member);
+            member = predecessors[&member];
+        }
+        // predecessors lead backwards, turn the cycle around and start at `current`
+        nodes.reverse();
+        nodes.rotate_right(1);
+        NegativeCycle { nodes }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NegativeCycle;
+    use crate::graph::{Graph, Node};
+
+    #[test]
+    fn test_bellman_ford_with_negative_weights() {
+        let mut graph: Graph<&str> = Graph::new();
+        let a = graph.insert(Node::new("a"));
+        let b = graph.insert(Node::new("b"));
+        let c = graph.insert(Node::new("c"));
+        let d = graph.insert(Node::new("d"));
+
+        graph.connect(a, b, 4.0);
+        graph.connect(a, c, 2.0);
+        graph.connect(b, c, -3.0);
+        graph.connect(c, d, 1.0);
+
+        let paths = graph.bellman_ford(a).unwrap();
+        assert_eq!(paths.distance(c), Some(1.0));
+        assert_eq!(paths.distance(d), Some(2.0));
+        assert_eq!(paths.path_to(d), Some(vec![a, b, c, d]));
+    }
+
+    #[test]
+    fn test_bellman_ford_reports_negative_cycle() {
+        let mut graph: Graph<&str> = Graph::new();
+        let a = graph.insert(Node::new("a"));
+        let b = graph.insert(Node::new("b"));
+        let c = graph.insert(Node::new("c"));
+        let unreachable = graph.insert(Node::new("unreachable"));
+
+        graph.connect(a, b, 1.0);
+        graph.connect(b, c, -2.0);
+        graph.connect(c, b, 1.0);
+        graph.connect(unreachable, a, 1.0);
+
+        let cycle = graph.bellman_ford(a).unwrap_err();
+        assert_eq!(cycle.nodes.len(), 2);
+        assert!(cycle.nodes.contains(&b) && cycle.nodes.contains(&c));
+
+        assert!(matches!(
+            graph.bellman_ford(unreachable),
+            Err(NegativeCycle { .. })
+        ));
+    }
+}
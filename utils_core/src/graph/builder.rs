@@ -0,0 +1,160 @@
+use std::marker::PhantomData;
+
+use thiserror::Error;
+
+use super::{Directed, EdgeType, Graph, Node};
+
+/// Returned by [`GraphBuilder::build`] for names the `graph!` macro would reject
+/// at compile time.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum BuildError {
+    #[error("Duplicate node identifier: `{0}`")]
+    DuplicateNode(String),
+
+    #[error("Node '{0}' not defined")]
+    UnknownNode(String),
+}
+
+/// Builds a [`Graph`] from named nodes and connections between those names,
+/// like the `graph!` macro does at compile time.
+///
+/// Connections may name nodes that are only added later, everything is checked
+/// by [`GraphBuilder::build`]. The names stay available through [`Graph::id`].
+#[derive(Debug)]
+pub struct GraphBuilder<N, E = f64, Ty = Directed> {
+    nodes: Vec<(String, N)>,
+    edges: Vec<(String, String, E)>,
+    _edge_type: PhantomData<Ty>,
+}
+
+impl<N, E, Ty> GraphBuilder<N, E, Ty>
+where
+    Ty: EdgeType,
+{
+    pub fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            _edge_type: PhantomData,
+        }
+    }
+
+    /// Adds a node called `name`, like `name = value` in `graph!`.
+    pub fn node(mut self, name: impl Into<String>, value: impl Into<N>) -> Self {
+        self.nodes.push((name.into(), value.into()));
+        self
+    }
+
+    /// Connects `from` to `to`, like `from -> to: weight` in `graph!`.
+    pub fn edge(mut self, from: impl Into<String>, to: impl Into<String>, weight: E) -> Self {
+        self.edges.push((from.into(), to.into(), weight));
+        self
+    }
+
+    /// Connects `from` and `to` both ways, like `from <-> to: weight` in `graph!`.
+    ///
+    /// Directed graphs get two connections, undirected graphs a single one.
+    pub fn undirected_edge(
+        mut self,
+        from: impl Into<String>,
+        to: impl Into<String>,
+        weight: E,
+    ) -> Self
+    where
+        E: Clone,
+    {
+        let (from, to) = (from.into(), to.into());
+        if !Ty::is_directed() {
+            self.edges.push((from, to, weight));
+            return self;
+        }
+
+        self.edges.push((from.clone(), to.clone(), weight.clone()));
+        self.edges.push((to, from, weight));
+        self
+    }
+
+    /// Builds the graph, inserting the nodes and then the connections in the
+    /// order they were added.
+    ///
+    /// Fails on the first name used for two nodes or connection to an unknown name.
+    pub fn build(self) -> Result<Graph<N, E, Ty>, BuildError> {
+        let mut graph = Graph::new();
+        for (name, value) in self.nodes {
+            if graph.id(&name).is_some() {
+                return Err(BuildError::DuplicateNode(name));
+            }
+            graph.insert_named(name, Node::new(value));
+        }
+
+        for (from, to, weight) in self.edges {
+            let from_id = graph.id(&from).ok_or(BuildError::UnknownNode(from))?;
+            let to_id = graph.id(&to).ok_or(BuildError::UnknownNode(to))?;
+            graph.connect(from_id, to_id, weight);
+        }
+
+        Ok(graph)
+    }
+}
+
+impl<N, E, Ty> Default for GraphBuilder<N, E, Ty>
+where
+    Ty: EdgeType,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BuildError, GraphBuilder};
+    use crate::graph::{Graph, UnGraph};
+
+    #[test]
+    fn test_builder_matches_macro_input() {
+        let graph: Graph<String> = GraphBuilder::new()
+            .node("home", "Home")
+            .node("station", "Station")
+            .edge("station", "school", 18.0)
+            .node("school", "School")
+            .undirected_edge("home", "station", 3.0)
+            .build()
+            .unwrap();
+
+        let home = graph.id("home").unwrap();
+        let station = graph.id("station").unwrap();
+        let school = graph.id("school").unwrap();
+        assert_eq!(graph.edges().count(), 3);
+        assert_eq!(graph.edge_weight(station, home), Some(&3.0));
+        assert_eq!(
+            graph.shortest_path(home, school).map(|(cost, _)| cost),
+            Some(21.0)
+        );
+
+        let undirected: UnGraph<&str> = GraphBuilder::new()
+            .node("a", "a")
+            .node("b", "b")
+            .undirected_edge("a", "b", 1.0)
+            .build()
+            .unwrap();
+        assert_eq!(undirected.edges().count(), 1);
+    }
+
+    #[test]
+    fn test_builder_rejects_invalid_names() {
+        let duplicate: Result<Graph<i32>, _> =
+            GraphBuilder::new().node("a", 1).node("a", 2).build();
+        assert_eq!(
+            duplicate.unwrap_err(),
+            BuildError::DuplicateNode("a".to_string())
+        );
+
+        let unknown: Result<Graph<i32>, _> =
+            GraphBuilder::new().node("a", 1).edge("a", "b", 1.0).build();
+        assert_eq!(
+            unknown.unwrap_err(),
+            BuildError::UnknownNode("b".to_string())
+        );
+    }
+}
@@ -0,0 +1,189 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use super::{Directed, EdgeType, Graph, Node, NodeID, union_find::UnionFind};
+
+impl<N, E, Ty> Graph<N, E, Ty>
+where
+    Ty: EdgeType,
+{
+    /// Groups the nodes into sets that can all reach each other, using Tarjan's algorithm.
+    ///
+    /// The components come in topological order, so connections only lead from a
+    /// component to a later one. The nodes of each component are sorted by `NodeID`.
+    /// For undirected graphs these are the same as [`Graph::connected_components`].
+    pub fn strongly_connected_components(&self) -> Vec<Vec<NodeID>> {
+        let mut tarjan = Tarjan::default();
+        for &root in self.nodes.keys() {
+            if !tarjan.indices.contains_key(&root) {
+                tarjan.run(self, root);
+            }
+        }
+
+        // Tarjan finds the components in reverse topological order
+        tarjan.components.reverse();
+        tarjan.components
+    }
+
+    /// Groups the nodes into sets that are connected when directions are ignored.
+    ///
+    /// The components are ordered by their lowest `NodeID`, and the nodes of each
+    /// component are sorted by `NodeID`.
+    pub fn connected_components(&self) -> Vec<Vec<NodeID>> {
+        let mut sets = UnionFind::new();
+        for (from_id, to_id, _) in self.edges() {
+            sets.union(from_id, to_id);
+        }
+
+        let mut components: BTreeMap<NodeID, Vec<NodeID>> = BTreeMap::new();
+        for &id in self.nodes.keys() {
+            components.entry(sets.find(id)).or_default().push(id);
+        }
+
+        let mut components: Vec<Vec<NodeID>> = components.into_values().collect();
+        components.sort_by_key(|component| component[0]);
+        components
+    }
+}
+
+/// State of Tarjan's algorithm, shared between the walks from every root.
+#[derive(Default)]
+struct Tarjan {
+    next_index: usize,
+    indices: HashMap<NodeID, usize>,
+    lowlinks: HashMap<NodeID, usize>,
+    stack: Vec<NodeID>,
+    on_stack: HashSet<NodeID>,
+    /// nodes whose successors are being walked, with how many of those were handled
+    call_stack: Vec<(NodeID, Vec<NodeID>, usize)>,
+    components: Vec<Vec<NodeID>>,
+}
+
+impl Tarjan {
+    /// Walks everything reachable from `root` without recursing.
+    fn run<N, E, Ty: EdgeType>(&mut self, graph: &Graph<N, E, Ty>, root: NodeID) {
+        self.visit(graph, root);
+
+        while let Some((id, successors, handled)) = self.call_stack.last_mut() {
+            let id = *id;
+            if let Some(&next) = successors.get(*handled) {
+                *handled += 1;
+                if !self.indices.contains_key(&next) {
+                    self.visit(graph, next);
+                } else if self.on_stack.contains(&next) {
+                    self.lower(id, self.indices[&next]);
+                }
+                continue;
+            }
+
+            self.call_stack.pop();
+            if let Some(&(parent, _, _)) = self.call_stack.last() {
+                self.lower(parent, self.lowlinks[&id]);
+            }
+
+            if self.lowlinks[&id] == self.indices[&id] {
+                let mut component = Vec::new();
+                while let Some(member) = self.stack.pop() {
+                    self.on_stack.remove(&member);
+                    component.push(member);
+                    if member == id {
+                        break;
+                    }
+                }
+                component.sort();
+                self.components.push(component);
+            }
+        }
+    }
+
+    fn visit<N, E, Ty: EdgeType>(&mut self, graph: &Graph<N, E, Ty>, id: NodeID) {
+        self.indices.insert(id, self.next_index);
+        self.lowlinks.insert(id, self.next_index);
+        self.next_index += 1;
+        self.stack.push(id);
+        self.on_stack.insert(id);
+        self.call_stack.push((id, graph.neighbors(id).collect(), 0));
+    }
+
+    fn lower(&mut self, id: NodeID, index: usize) {
+        let lowlink = self.lowlinks[&id].min(index);
+        self.lowlinks.insert(id, lowlink);
+    }
+}
+
+impl<N, E> Graph<N, E, Directed> {
+    /// Builds a graph with one node per strongly connected component.
+    ///
+    /// The node values are the original `NodeID`s of each component and the
+    /// nodes are inserted in the order of [`Graph::strongly_connected_components`].
+    /// Every connection between two different components is kept, connections
+    /// inside a component are dropped.
+    pub fn condensation(&self) -> Graph<Vec<NodeID>, E>
+    where
+        E: Clone,
+    {
+        let mut condensed = Graph::new();
+        let mut component_of: HashMap<NodeID, NodeID> = HashMap::new();
+        for component in self.strongly_connected_components() {
+            let members = component.clone();
+            let component_id = condensed.insert(Node::new(component));
+            component_of.extend(members.into_iter().map(|id| (id, component_id)));
+        }
+
+        for (from_id, to_id, weight) in self.edges() {
+            let (Some(&from), Some(&to)) = (component_of.get(&from_id), component_of.get(&to_id))
+            else {
+                continue;
+            };
+            if from != to {
+                condensed.connect(from, to, weight.clone());
+            }
+        }
+
+        condensed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{Graph, Node, NodeID};
+
+    #[test]
+    fn test_components_of_transit_graph() {
+        // home <-> station <-> school, home -> somewhere_else
+        let mut graph: Graph<&str> = Graph::new();
+        let home = graph.insert(Node::new("home"));
+        let station = graph.insert(Node::new("station"));
+        let school = graph.insert(Node::new("school"));
+        let somewhere_else = graph.insert(Node::new("somewhere_else"));
+        let island = graph.insert(Node::new("island"));
+
+        graph.connect_bidirectionally(home, station, 3.0);
+        graph.connect_bidirectionally(station, school, 18.0);
+        graph.connect(home, somewhere_else, 5.0);
+
+        assert_eq!(
+            graph.strongly_connected_components(),
+            vec![
+                vec![island],
+                vec![home, station, school],
+                vec![somewhere_else]
+            ]
+        );
+        assert_eq!(
+            graph.connected_components(),
+            vec![vec![home, station, school, somewhere_else], vec![island]]
+        );
+
+        let condensed = graph.condensation();
+        let values: Vec<&Vec<NodeID>> = condensed.iter().map(|(_, node)| &node.value).collect();
+        assert_eq!(values.len(), 3);
+        assert_eq!(
+            condensed
+                .edges()
+                .map(|(from, to, _)| (from, to))
+                .collect::<Vec<_>>(),
+            vec![(NodeID::from(1), NodeID::from(2))]
+        );
+        assert!(condensed.toposort().is_ok());
+    }
+}
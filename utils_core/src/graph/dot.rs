@@ -0,0 +1,645 @@
+use std::{
+    collections::HashMap,
+    fmt::{Display, Write},
+};
+
+use thiserror::Error;
+
+use super::{EdgeType, Graph, Node, NodeID};
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum DotError {
+    #[error("Unexpected {found} at line {line}, expected {expected}")]
+    UnexpectedToken {
+        expected: &'static str,
+        found: String,
+        line: usize,
+    },
+
+    #[error("Unexpected end of input, expected {expected}")]
+    UnexpectedEnd { expected: &'static str },
+
+    #[error("Unterminated {what} starting at line {line}")]
+    Unterminated { what: &'static str, line: usize },
+
+    #[error("Invalid weight `{value}` at line {line}")]
+    InvalidWeight { value: String, line: usize },
+
+    #[error("Unsupported DOT feature at line {line}: {feature}")]
+    Unsupported { feature: &'static str, line: usize },
+}
+
+/// DOT attributes as `(name, value)` pairs
+type Attributes = Vec<(String, String)>;
+
+impl<N, E, Ty> Graph<N, E, Ty>
+where
+    Ty: EdgeType,
+{
+    /// Formats the graph in the Graphviz DOT language, labeling nodes with their
+    /// value and connections with their weight.
+    pub fn to_dot(&self) -> String
+    where
+        N: Display,
+        E: Display,
+    {
+        self.to_dot_with(
+            |_, node| node.value.to_string(),
+            |weight| vec![("weight".to_string(), weight.to_string())],
+        )
+    }
+
+    /// Formats the graph in the Graphviz DOT language.
+    ///
+    /// `node_label` gives the label of every node, and `edge_attributes` the
+    /// attributes of every connection as `(name, value)` pairs. Both are quoted
+    /// and escaped here. Nodes are named after their `NodeID`.
+    ///
+    /// Directed graphs write two opposite connections with the same attributes
+    /// as a single `dir=both` connection.
+    pub fn to_dot_with(
+        &self,
+        node_label: impl Fn(NodeID, &Node<N>) -> String,
+        edge_attributes: impl Fn(&E) -> Attributes,
+    ) -> String {
+        let (keyword, arrow) = if Ty::is_directed() {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+
+        let mut dot = format!("{keyword} {{\n");
+        for (id, node) in self.iter() {
+            let _ = writeln!(
+                dot,
+                "    {} [label={}];",
+                id.0,
+                quote(&node_label(*id, node))
+            );
+        }
+
+        // (from, to, attributes, both directions)
+        let mut edges: Vec<(NodeID, NodeID, Attributes, bool)> = Vec::new();
+        let mut unpaired: HashMap<(NodeID, NodeID, Attributes), Vec<usize>> = HashMap::new();
+        for (from_id, to_id, weight) in self.edges() {
+            let attributes = edge_attributes(weight);
+            if Ty::is_directed() && from_id != to_id {
+                let reverse = (to_id, from_id, attributes.clone());
+                if let Some(index) = unpaired.get_mut(&reverse).and_then(Vec::pop) {
+                    edges[index].3 = true;
+                    continue;
+                }
+                unpaired
+                    .entry((from_id, to_id, attributes.clone()))
+                    .or_default()
+                    .push(edges.len());
+            }
+            edges.push((from_id, to_id, attributes, false));
+        }
+
+        for (from_id, to_id, mut attributes, both) in edges {
+            if both {
+                attributes.push(("dir".to_string(), "both".to_string()));
+            }
+            let _ = write!(dot, "    {} {arrow} {}", from_id.0, to_id.0);
+            if !attributes.is_empty() {
+                let attributes: Vec<String> = attributes
+                    .iter()
+                    .map(|(name, value)| format!("{}={}", attribute_name(name), quote(value)))
+                    .collect();
+                let _ = write!(dot, " [{}]", attributes.join(", "));
+            }
+            dot.push_str(";\n");
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+impl Graph<String> {
+    /// Reads a graph from the Graphviz DOT language.
+    ///
+    /// Node values are taken from the `label` attribute, falling back to the node
+    /// name, and connection weights from the `weight` attribute, falling back to `1.0`.
+    /// NaN and infinite weights are rejected.
+    /// Nodes can be looked up by their name with [`Graph::id`].
+    /// Undirected `--` connections and `dir=both` connections are added in both
+    /// directions. Subgraphs and ports are not supported.
+    pub fn from_dot(input: &str) -> Result<Self, DotError> {
+        Parser::new(tokenize(input)?).parse_graph()
+    }
+}
+
+/// Quotes and escapes a DOT identifier.
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Writes attribute names as plain identifiers where possible.
+fn attribute_name(name: &str) -> String {
+    let plain = name.starts_with(|c: char| c.is_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+    if plain { name.to_string() } else { quote(name) }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Id(String),
+    Arrow,
+    Line,
+    BraceOpen,
+    BraceClose,
+    BracketOpen,
+    BracketClose,
+    Equal,
+    Semicolon,
+    Comma,
+    Colon,
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Id(id) => write!(f, "`{id}`"),
+            Token::Arrow => f.write_str("`->`"),
+            Token::Line => f.write_str("`--`"),
+            Token::BraceOpen => f.write_str("`{`"),
+            Token::BraceClose => f.write_str("`}`"),
+            Token::BracketOpen => f.write_str("`[`"),
+            Token::BracketClose => f.write_str("`]`"),
+            Token::Equal => f.write_str("`=`"),
+            Token::Semicolon => f.write_str("`;`"),
+            Token::Comma => f.write_str("`,`"),
+            Token::Colon => f.write_str("`:`"),
+        }
+    }
+}
+
+/// Splits DOT source into tokens, each with the line it starts on.
+fn tokenize(input: &str) -> Result<Vec<(Token, usize)>, DotError> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+    let mut line = 1;
+    let mut line_start = true;
+
+    while let Some(c) = chars.next() {
+        let token_line = line;
+        match c {
+            '\n' => {
+                line += 1;
+                line_start = true;
+                continue;
+            }
+            c if c.is_whitespace() => continue,
+            // preprocessor output lines
+            '#' if line_start => {
+                while chars.next_if(|&c| c != '\n').is_some() {}
+                continue;
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                while chars.next_if(|&c| c != '\n').is_some() {}
+                continue;
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut previous = ' ';
+                loop {
+                    let Some(c) = chars.next() else {
+                        return Err(DotError::Unterminated {
+                            what: "comment",
+                            line: token_line,
+                        });
+                    };
+                    if c == '\n' {
+                        line += 1;
+                    }
+                    if previous == '*' && c == '/' {
+                        break;
+                    }
+                    previous = c;
+                }
+                continue;
+            }
+            '-' if chars.peek() == Some(&'>') => {
+                chars.next();
+                tokens.push((Token::Arrow, token_line));
+            }
+            '-' if chars.peek() == Some(&'-') => {
+                chars.next();
+                tokens.push((Token::Line, token_line));
+            }
+            '{' => tokens.push((Token::BraceOpen, token_line)),
+            '}' => tokens.push((Token::BraceClose, token_line)),
+            '[' => tokens.push((Token::BracketOpen, token_line)),
+            ']' => tokens.push((Token::BracketClose, token_line)),
+            '=' => tokens.push((Token::Equal, token_line)),
+            ';' => tokens.push((Token::Semicolon, token_line)),
+            ',' => tokens.push((Token::Comma, token_line)),
+            ':' => tokens.push((Token::Colon, token_line)),
+            '"' => {
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        None => {
+                            return Err(DotError::Unterminated {
+                                what: "string",
+                                line: token_line,
+                            });
+                        }
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some('"') => value.push('"'),
+                            Some('\\') => value.push('\\'),
+                            // an escaped newline continues the string
+                            Some('\n') => line += 1,
+                            Some(other) => {
+                                value.push('\\');
+                                value.push(other);
+                            }
+                            None => continue,
+                        },
+                        Some(c) => {
+                            if c == '\n' {
+                                line += 1;
+                            }
+                            value.push(c);
+                        }
+                    }
+                }
+                tokens.push((Token::Id(value), token_line));
+            }
+            '<' => {
+                return Err(DotError::Unsupported {
+                    feature: "HTML strings",
+                    line: token_line,
+                });
+            }
+            c if c.is_alphanumeric() || c == '_' || c == '.' || c == '-' => {
+                let mut value = c.to_string();
+                while let Some(c) = chars.next_if(|&c| c.is_alphanumeric() || c == '_' || c == '.')
+                {
+                    value.push(c);
+                }
+                tokens.push((Token::Id(value), token_line));
+            }
+            other => {
+                return Err(DotError::UnexpectedToken {
+                    expected: "a DOT token",
+                    found: format!("`{other}`"),
+                    line: token_line,
+                });
+            }
+        }
+        line_start = false;
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: std::vec::IntoIter<(Token, usize)>,
+    peeked: Option<(Token, usize)>,
+    graph: Graph<String>,
+    directed: bool,
+}
+
+impl Parser {
+    fn new(tokens: Vec<(Token, usize)>) -> Self {
+        Self {
+            tokens: tokens.into_iter(),
+            peeked: None,
+            graph: Graph::new(),
+            directed: true,
+        }
+    }
+
+    fn peek(&mut self) -> Option<&Token> {
+        if self.peeked.is_none() {
+            self.peeked = self.tokens.next();
+        }
+        self.peeked.as_ref().map(|(token, _)| token)
+    }
+
+    fn next(&mut self, expected: &'static str) -> Result<(Token, usize), DotError> {
+        self.peeked
+            .take()
+            .or_else(|| self.tokens.next())
+            .ok_or(DotError::UnexpectedEnd { expected })
+    }
+
+    fn expect(&mut self, token: Token, expected: &'static str) -> Result<usize, DotError> {
+        match self.next(expected)? {
+            (found, line) if found == token => Ok(line),
+            (found, line) => Err(DotError::UnexpectedToken {
+                expected,
+                found: found.to_string(),
+                line,
+            }),
+        }
+    }
+
+    fn id(&mut self, expected: &'static str) -> Result<(String, usize), DotError> {
+        match self.next(expected)? {
+            (Token::Id(id), line) => Ok((id, line)),
+            (found, line) => Err(DotError::UnexpectedToken {
+                expected,
+                found: found.to_string(),
+                line,
+            }),
+        }
+    }
+
+    fn parse_graph(mut self) -> Result<Graph<String>, DotError> {
+        let (mut keyword, mut line) = self.id("`graph` or `digraph`")?;
+        if keyword.eq_ignore_ascii_case("strict") {
+            (keyword, line) = self.id("`graph` or `digraph`")?;
+        }
+        self.directed = match keyword.to_ascii_lowercase().as_str() {
+            "digraph" => true,
+            "graph" => false,
+            _ => {
+                return Err(DotError::UnexpectedToken {
+                    expected: "`graph` or `digraph`",
+                    found: format!("`{keyword}`"),
+                    line,
+                });
+            }
+        };
+
+        // optional graph name
+        if let Some(Token::Id(_)) = self.peek() {
+            self.next("graph name")?;
+        }
+        self.expect(Token::BraceOpen, "`{`")?;
+
+        loop {
+            match self.peek() {
+                Some(Token::BraceClose) => {
+                    self.next("`}`")?;
+                    break;
+                }
+                Some(Token::Semicolon) => {
+                    self.next("`;`")?;
+                }
+                _ => self.parse_statement()?,
+            }
+        }
+
+        match self.next("end of input") {
+            Err(_) => Ok(self.graph),
+            Ok((found, line)) => Err(DotError::UnexpectedToken {
+                expected: "end of input",
+                found: found.to_string(),
+                line,
+            }),
+        }
+    }
+
+    fn parse_statement(&mut self) -> Result<(), DotError> {
+        let (id, line) = match self.next("a statement")? {
+            (Token::Id(id), line) => (id, line),
+            (Token::BraceOpen, line) => {
+                return Err(DotError::Unsupported {
+                    feature: "subgraphs",
+                    line,
+                });
+            }
+            (found, line) => {
+                return Err(DotError::UnexpectedToken {
+                    expected: "a statement",
+                    found: found.to_string(),
+                    line,
+                });
+            }
+        };
+
+        let keyword = id.to_ascii_lowercase();
+        if keyword == "subgraph" {
+            return Err(DotError::Unsupported {
+                feature: "subgraphs",
+                line,
+            });
+        }
+        // `graph [..]`, `node [..]` and `edge [..]` set defaults, which are ignored
+        if matches!(keyword.as_str(), "graph" | "node" | "edge")
+            && self.peek() == Some(&Token::BracketOpen)
+        {
+            self.parse_attributes()?;
+            return Ok(());
+        }
+
+        match self.peek() {
+            Some(Token::Equal) => {
+                // graph attribute `name = value`
+                self.next("`=`")?;
+                self.id("an attribute value")?;
+                Ok(())
+            }
+            Some(Token::Arrow | Token::Line) => self.parse_edges(id),
+            Some(Token::Colon) => Err(DotError::Unsupported {
+                feature: "ports",
+                line,
+            }),
+            _ => {
+                let attributes = self.parse_attributes()?;
+                let node_id = self.node(&id);
+                if let Some((_, label)) = attributes.into_iter().find(|(name, _)| name == "label")
+                    && let Some(node) = self.graph.get_node_mut(&node_id)
+                {
+                    node.value = label;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn parse_edges(&mut self, first: String) -> Result<(), DotError> {
+        let mut chain = vec![first];
+        let mut line = 0;
+        while let Some(Token::Arrow | Token::Line) = self.peek() {
+            let (arrow, arrow_line) = self.next("`->` or `--`")?;
+            let expected = if self.directed {
+                Token::Arrow
+            } else {
+                Token::Line
+            };
+            if arrow != expected {
+                return Err(DotError::UnexpectedToken {
+                    expected: if self.directed { "`->`" } else { "`--`" },
+                    found: arrow.to_string(),
+                    line: arrow_line,
+                });
+            }
+            let (id, id_line) = self.id("a node name")?;
+            chain.push(id);
+            line = id_line;
+        }
+
+        let attributes = self.parse_attributes()?;
+        let mut weight = 1.0;
+        let mut both = !self.directed;
+        for (name, value) in attributes {
+            match name.as_str() {
+                "weight" => {
+                    weight = value
+                        .parse::<f64>()
+                        .ok()
+                        .filter(|weight| weight.is_finite())
+                        .ok_or(DotError::InvalidWeight { value, line })?;
+                }
+                "dir" if value == "both" => both = true,
+                _ => {}
+            }
+        }
+
+        let ids: Vec<NodeID> = chain.iter().map(|name| self.node(name)).collect();
+        for pair in ids.windows(2) {
+            if both {
+                self.graph.connect_bidirectionally(pair[0], pair[1], weight);
+            } else {
+                self.graph.connect(pair[0], pair[1], weight);
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses any number of `[name=value, ...]` lists.
+    fn parse_attributes(&mut self) -> Result<Vec<(String, String)>, DotError> {
+        let mut attributes = Vec::new();
+        while self.peek() == Some(&Token::BracketOpen) {
+            self.next("`[`")?;
+            loop {
+                match self.peek() {
+                    Some(Token::BracketClose) => {
+                        self.next("`]`")?;
+                        break;
+                    }
+                    Some(Token::Comma | Token::Semicolon) => {
+                        self.next("`,`")?;
+                    }
+                    _ => {
+                        let (name, _) = self.id("an attribute name")?;
+                        self.expect(Token::Equal, "`=`")?;
+                        let (value, _) = self.id("an attribute value")?;
+                        attributes.push((name, value));
+                    }
+                }
+            }
+        }
+        Ok(attributes)
+    }
+
+    /// The node called `name`, inserting it if this is its first mention.
+    fn node(&mut self, name: &str) -> NodeID {
+        match self.graph.id(name) {
+            Some(id) => id,
+            None => self.graph.insert_named(name, Node::new(name)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DotError;
+    use crate::graph::{Graph, Node, UnGraph};
+
+    #[test]
+    fn test_to_dot_collapses_bidirectional_pairs() {
+        let mut graph: Graph<&str> = Graph::new();
+        let home = graph.insert(Node::new("Home"));
+        let station = graph.insert(Node::new("Station \"Central\""));
+        let elsewhere = graph.insert(Node::new("Somewhere"));
+
+        graph.connect_bidirectionally(home, station, 3.0);
+        graph.connect(home, elsewhere, 5.0);
+        graph.connect(elsewhere, home, 2.0);
+
+        assert_eq!(
+            graph.to_dot(),
+            r#"digraph {
+    0 [label="Home"];
+    1 [label="Station \"Central\""];
+    2 [label="Somewhere"];
+    0 -> 1 [weight="3", dir="both"];
+    0 -> 2 [weight="5"];
+    2 -> 0 [weight="2"];
+}
+"#
+        );
+
+        let mut undirected: UnGraph<i32, &str> = UnGraph::new();
+        let a = undirected.insert(Node::new(1));
+        let b = undirected.insert(Node::new(2));
+        undirected.connect(a, b, "cable");
+        assert_eq!(
+            undirected.to_dot_with(
+                |id, node| format!("{id:?}: {}", node.value),
+                |kind| vec![("label".to_string(), kind.to_string())]
+            ),
+            "graph {\n    0 [label=\"NodeID(0): 1\"];\n    1 [label=\"NodeID(1): 2\"];\n    0 -- 1 [label=\"cable\"];\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_dot_round_trip() {
+        let mut graph: Graph<String> = Graph::new();
+        let a = graph.insert(Node::new("a"));
+        let b = graph.insert(Node::new("b"));
+        let c = graph.insert(Node::new("c"));
+        graph.connect_bidirectionally(a, b, 2.5);
+        graph.connect(b, c, 1.0);
+
+        let parsed = Graph::from_dot(&graph.to_dot()).unwrap();
+        assert_eq!(parsed.to_dot(), graph.to_dot());
+    }
+
+    #[test]
+    fn test_from_dot() {
+        let source = r#"
+            // transit
+            strict graph transit {
+                node [shape=box];
+                rankdir = LR;
+                home [label="Home"]
+                home -- station -- school [weight=4];
+                /* a
+                   comment */
+                school -- "far away";
+            }
+        "#;
+
+        let graph = Graph::from_dot(source).unwrap();
+        let values: Vec<&str> = graph.iter().map(|(_, node)| node.value.as_str()).collect();
+        assert_eq!(values, vec!["Home", "station", "school", "far away"]);
+        assert_eq!(graph.id("home"), Some(0.into()));
+        assert_eq!(graph.edges().count(), 6);
+        assert_eq!(graph.edge_weight(2.into(), 1.into()), Some(&4.0));
+
+        assert_eq!(
+            Graph::from_dot("digraph {\n a -> b [weight=heavy];\n}").unwrap_err(),
+            DotError::InvalidWeight {
+                value: "heavy".to_string(),
+                line: 2
+            }
+        );
+        for weight in ["nan", "inf"] {
+            let source = format!("digraph {{\n a -> b [weight={weight}];\n}}");
+            assert_eq!(
+                Graph::from_dot(&source).unwrap_err(),
+                DotError::InvalidWeight {
+                    value: weight.to_string(),
+                    line: 2
+                }
+            );
+        }
+        assert!(matches!(
+            Graph::from_dot("digraph {\n\n a -- b }"),
+            Err(DotError::UnexpectedToken { line: 3, .. })
+        ));
+        assert!(matches!(
+            Graph::from_dot("digraph { subgraph x { a } }"),
+            Err(DotError::Unsupported { .. })
+        ));
+    }
+}
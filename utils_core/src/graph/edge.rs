@@ -0,0 +1,151 @@
+use thiserror::Error;
+
+use super::NodeID;
+
+/// Marks whether the connections of a [`Graph`](super::Graph) have a direction.
+pub trait EdgeType {
+    fn is_directed() -> bool;
+}
+
+/// Connections lead from one node to another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Directed;
+
+/// Connections can be walked both ways and are stored only once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Undirected;
+
+impl EdgeType for Directed {
+    fn is_directed() -> bool {
+        true
+    }
+}
+
+impl EdgeType for Undirected {
+    fn is_directed() -> bool {
+        false
+    }
+}
+
+/// Connection payloads that can be used as a cost by the weighted algorithms of a
+/// [`Graph`](super::Graph).
+pub trait EdgeWeight {
+    /// cost of traversing the connection
+    fn weight(&self) -> f64;
+}
+
+/// Returned when a connection is rejected by [`Graph::try_connect`](super::Graph::try_connect),
+/// either for one of its nodes or for its weight.
+#[derive(Error, Debug, Clone, Copy, PartialEq)]
+pub enum ConnectError {
+    #[error("Connection to a node that is not in the graph: {0:?}")]
+    UnknownNode(NodeID),
+
+    #[error("Connection weight is NaN")]
+    NotANumber,
+
+    #[error("Connection weight is infinite: {0}")]
+    Infinite(f64),
+
+    #[error("Connection weight is negative: {0}, negative weights have to be allowed first")]
+    Negative(f64),
+}
+
+impl ConnectError {
+    /// Checks `weight` on its own, rejecting negative values unless `allow_negative` is set.
+    pub fn check(weight: f64, allow_negative: bool) -> Result<(), ConnectError> {
+        if weight.is_nan() {
+            Err(ConnectError::NotANumber)
+        } else if weight.is_infinite() {
+            Err(ConnectError::Infinite(weight))
+        } else if weight < 0.0 && !allow_negative {
+            Err(ConnectError::Negative(weight))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Connection payloads with a value for connections that are declared without one,
+/// like `a -> b` in the `graph!` macro.
+pub trait UnitEdge {
+    fn unit() -> Self;
+}
+
+macro_rules! impl_numeric_edge {
+    ($($ty:ty),*) => {
+        $(
+            impl EdgeWeight for $ty {
+                fn weight(&self) -> f64 {
+                    *self as f64
+                }
+            }
+
+            impl UnitEdge for $ty {
+                fn unit() -> Self {
+                    1 as $ty
+                }
+            }
+        )*
+    };
+}
+
+impl_numeric_edge!(f32, f64, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+/// Weights `graph!` can take from an unsuffixed integer literal like `a -> b: 3`.
+///
+/// Integers take the literal as their own type, so it is checked like any other
+/// integer literal, while floats convert it.
+#[doc(hidden)]
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` weights can't be given as integer literals in `graph!`"
+)]
+pub trait IntegerWeight {
+    type Literal;
+
+    fn from_literal(literal: Self::Literal) -> Self;
+}
+
+macro_rules! impl_integer_weight {
+    ($($ty:ty),*) => {
+        $(
+            impl IntegerWeight for $ty {
+                type Literal = $ty;
+
+                fn from_literal(literal: $ty) -> Self {
+                    literal
+                }
+            }
+        )*
+    };
+}
+
+impl_integer_weight!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+);
+
+impl IntegerWeight for f32 {
+    type Literal = i64;
+
+    fn from_literal(literal: i64) -> Self {
+        literal as f32
+    }
+}
+
+impl IntegerWeight for f64 {
+    type Literal = i64;
+
+    fn from_literal(literal: i64) -> Self {
+        literal as f64
+    }
+}
+
+impl EdgeWeight for () {
+    fn weight(&self) -> f64 {
+        1.0
+    }
+}
+
+impl UnitEdge for () {
+    fn unit() -> Self {}
+}
@@ -0,0 +1,193 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use super::{EdgeType, EdgeWeight, Graph, NodeID};
+
+/// The result of [`Graph::max_flow`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct MaxFlow {
+    /// total flow from the source to the sink
+    pub value: f64,
+    /// flow over every connection as `(from, to, flow)` in the order the connections
+    /// were made, undirected connections are oriented in the direction of their flow
+    pub flows: Vec<(NodeID, NodeID, f64)>,
+    /// the saturated connections that separate the source from the sink,
+    /// oriented from the source side to the sink side
+    pub min_cut: Vec<(NodeID, NodeID)>,
+}
+
+/// One direction of a connection in the residual network
+struct Arc {
+    to: NodeID,
+    capacity: f64,
+    flow: f64,
+}
+
+impl Arc {
+    fn residual(&self) -> f64 {
+        self.capacity - self.flow
+    }
+}
+
+impl<N, E, Ty> Graph<N, E, Ty>
+where
+    E: EdgeWeight,
+    Ty: EdgeType,
+{
+    /// Computes the maximum flow from `source` to `sink` using the Edmonds-Karp algorithm,
+    /// treating connection weights as capacities.
+    ///
+    /// Returns `None` if either node doesn't exist.
+    pub fn max_flow(&self, source: NodeID, sink: NodeID) -> Option<MaxFlow> {
+        if !self.nodes.contains_key(&source) || !self.nodes.contains_key(&sink) {
+            return None;
+        }
+
+        // connection `i` becomes the arcs `2 * i` and `2 * i + 1`, so the
+        // opposite arc of `arc` is always `arc ^ 1`
+        let mut arcs: Vec<Arc> = Vec::new();
+        let mut leaving: HashMap<NodeID, Vec<usize>> = HashMap::new();
+        let mut connections = Vec::new();
+        for (from_id, to_id, weight) in self.edges() {
+            let capacity = weight.weight();
+            // an undirected connection can carry its capacity either way
+            let reverse_capacity = if Ty::is_directed() { 0.0 } else { capacity };
+
+            leaving.entry(from_id).or_default().push(arcs.len());
+            arcs.push(Arc {
+                to: to_id,
+                capacity,
+                flow: 0.0,
+            });
+            leaving.entry(to_id).or_default().push(arcs.len());
+            arcs.push(Arc {
+                to: from_id,
+                capacity: reverse_capacity,
+                flow: 0.0,
+            });
+            connections.push((from_id, to_id));
+        }
+
+        let mut value = 0.0;
+        let reachable = loop {
+            // breadth-first search for the shortest augmenting path
+            let mut parent_arc: HashMap<NodeID, usize> = HashMap::new();
+            let mut reachable = HashSet::from([source]);
+            let mut queue = VecDeque::from([source]);
+            while let Some(id) = queue.pop_front() {
+                if id == sink {
+                    break;
+                }
+                for &arc in leaving.get(&id).into_iter().flatten() {
+                    let to = arcs[arc].to;
+                    if arcs[arc].residual() > 0.0 && reachable.insert(to) {
+                        parent_arc.insert(to, arc);
+                        queue.push_back(to);
+                    }
+                }
+            }
+
+            if source == sink || !reachable.contains(&sink) {
+                break reachable;
+            }
+
+            let mut path = Vec::new();
+            let mut current = sink;
+            while current != source {
+                let arc = parent_arc[&current];
+                path.push(arc);
+                current = arcs[arc ^ 1].to;
+            }
+
+            let bottleneck = path
+                .iter()
+                .map(|&arc| arcs[arc].residual())
+                .fold(f64::INFINITY, f64::min);
+            for arc in path {
+                arcs[arc].flow += bottleneck;
+                arcs[arc ^ 1].flow -= bottleneck;
+            }
+            value += bottleneck;
+        };
+
+        let flows = connections
+            .iter()
+            .enumerate()
+            .map(|(i, &(from_id, to_id))| {
+                let flow = arcs[2 * i].flow;
+                if flow < 0.0 {
+                    (to_id, from_id, -flow)
+                } else {
+                    (from_id, to_id, flow)
+                }
+            })
+            .collect();
+
+        let min_cut = connections
+            .iter()
+            .filter_map(|&(from_id, to_id)| {
+                match (reachable.contains(&from_id), reachable.contains(&to_id)) {
+                    (true, false) => Some((from_id, to_id)),
+                    (false, true) if !Ty::is_directed() => Some((to_id, from_id)),
+                    _ => None,
+                }
+            })
+            .collect();
+
+        Some(MaxFlow {
+            value,
+            flows,
+            min_cut,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{Graph, Node, UnGraph};
+
+    #[test]
+    fn test_max_flow_and_min_cut() {
+        let mut graph: Graph<&str> = Graph::new();
+        let s = graph.insert(Node::new("s"));
+        let a = graph.insert(Node::new("a"));
+        let b = graph.insert(Node::new("b"));
+        let t = graph.insert(Node::new("t"));
+
+        graph.connect(s, a, 3.0);
+        graph.connect(s, b, 2.0);
+        graph.connect(a, b, 1.0);
+        graph.connect(a, t, 2.0);
+        graph.connect(b, t, 3.0);
+
+        let flow = graph.max_flow(s, t).unwrap();
+        assert_eq!(flow.value, 5.0);
+        assert_eq!(
+            flow.flows,
+            vec![
+                (s, a, 3.0),
+                (s, b, 2.0),
+                (a, b, 1.0),
+                (a, t, 2.0),
+                (b, t, 3.0)
+            ]
+        );
+        assert_eq!(flow.min_cut, vec![(s, a), (s, b)]);
+        assert!(graph.max_flow(s, 42.into()).is_none());
+    }
+
+    #[test]
+    fn test_max_flow_undirected() {
+        let mut graph: UnGraph<&str> = UnGraph::new();
+        let s = graph.insert(Node::new("s"));
+        let a = graph.insert(Node::new("a"));
+        let t = graph.insert(Node::new("t"));
+
+        graph.connect(a, s, 4.0);
+        graph.connect(t, a, 1.5);
+
+        let flow = graph.max_flow(s, t).unwrap();
+        assert_eq!(flow.value, 1.5);
+        assert_eq!(flow.flows, vec![(s, a, 1.5), (a, t, 1.5)]);
+        assert_eq!(flow.min_cut, vec![(a, t)]);
+    }
+}
@@ -0,0 +1,700 @@
+mod all_pairs;
+mod bellman_ford;
+mod builder;
+mod components;
+mod dot;
+mod edge;
+mod max_flow;
+#[cfg(feature = "serde")]
+mod serialize;
+mod shortest_path;
+mod spanning_tree;
+mod toposort;
+mod traversal;
+mod union_find;
+
+use std::{
+    collections::{BTreeMap, btree_map},
+    marker::PhantomData,
+    slice,
+};
+
+pub use all_pairs::{AllPairsAlgorithm, DistanceMatrix};
+pub use bellman_ford::{NegativeCycle, ShortestPaths};
+pub use builder::{BuildError, GraphBuilder};
+pub use dot::DotError;
+pub use edge::{ConnectError, Directed, EdgeType, EdgeWeight, IntegerWeight, Undirected, UnitEdge};
+pub use max_flow::MaxFlow;
+pub use spanning_tree::SpanningForest;
+pub use toposort::Cycle;
+pub use traversal::{Bfs, Dfs, DfsEvent, DfsPostOrder, Walker, WalkerIter};
+
+/// A graph with node values of type `N` and connections carrying an `E`,
+/// which is the connection weight by default.
+///
+/// Nodes are iterated in `NodeID` (and therefore insertion) order and connections
+/// in the order they were made, so the output doesn't change between runs.
+///
+/// `Ty` decides whether connections are [`Directed`] or [`Undirected`]. An undirected
+/// connection is stored once and is reported as leaving and entering both of its nodes.
+#[derive(Debug)]
+pub struct Graph<N, E = f64, Ty = Directed> {
+    nodes: BTreeMap<NodeID, Node<N>>,
+    /// removed connections leave a `None` behind so the indices stay valid
+    connections: Vec<Option<(NodeID, NodeID, E)>>,
+    /// indices into `connections` of the edges leaving a node
+    outgoing: BTreeMap<NodeID, Vec<usize>>,
+    /// indices into `connections` of the edges entering a node
+    incoming: BTreeMap<NodeID, Vec<usize>>,
+    next_id: i32,
+    /// names given to nodes with `insert_named`
+    names: BTreeMap<String, NodeID>,
+    /// whether `try_connect` accepts negative weights
+    allow_negative_weights: bool,
+    _edge_type: PhantomData<Ty>,
+}
+
+/// A graph whose connections have a direction
+pub type DiGraph<N, E = f64> = Graph<N, E, Directed>;
+
+/// A graph whose connections can be walked both ways
+pub type UnGraph<N, E = f64> = Graph<N, E, Undirected>;
+
+impl<N, E, Ty> Graph<N, E, Ty>
+where
+    Ty: EdgeType,
+{
+    pub fn new() -> Self {
+        Self {
+            nodes: BTreeMap::new(),
+            connections: Vec::new(),
+            outgoing: BTreeMap::new(),
+            incoming: BTreeMap::new(),
+            next_id: 0,
+            names: BTreeMap::new(),
+            allow_negative_weights: false,
+            _edge_type: PhantomData,
+        }
+    }
+
+    /// Whether the connections of this graph have a direction.
+    pub fn is_directed(&self) -> bool {
+        Ty::is_directed()
+    }
+
+    /// IDs are handed out monotonically and never reused, even after a node was removed.
+    fn get_next_id(&mut self) -> NodeID {
+        let id = self.next_id;
+        self.next_id += 1;
+        id.into()
+    }
+
+    pub fn insert(&mut self, value: Node<N>) -> NodeID {
+        let id = self.get_next_id();
+        self.nodes.insert(id, value);
+        self.outgoing.insert(id, Vec::new());
+        self.incoming.insert(id, Vec::new());
+        id
+    }
+
+    /// Inserts a node that can be looked up by `name` with [`Graph::id`].
+    ///
+    /// If `name` was already taken it moves to the new node.
+    pub fn insert_named(&mut self, name: impl Into<String>, value: Node<N>) -> NodeID {
+        let id = self.insert(value);
+        self.names.insert(name.into(), id);
+        id
+    }
+
+    /// The node inserted under `name` with [`Graph::insert_named`].
+    pub fn id(&self, name: &str) -> Option<NodeID> {
+        self.names.get(name).copied()
+    }
+
+    /// Connects `from_id` to `to_id` without checking the weight,
+    /// see [`Graph::try_connect`] for a checked version.
+    ///
+    /// Panics if either node is not in the graph, for example because it was removed.
+    pub fn connect(&mut self, from_id: NodeID, to_id: NodeID, weight: E) {
+        for id in [from_id, to_id] {
+            assert!(self.nodes.contains_key(&id), "{id:?} is not in the graph");
+        }
+
+        let index = self.connections.len();
+        self.connections.push(Some((from_id, to_id, weight)));
+        self.outgoing.entry(from_id).or_default().push(index);
+        self.incoming.entry(to_id).or_default().push(index);
+    }
+
+    /// Connects both nodes in both directions.
+    ///
+    /// Directed graphs get two connections, undirected graphs a single one.
+    pub fn connect_bidirectionally(&mut self, from_id: NodeID, to_id: NodeID, weight: E)
+    where
+        E: Clone,
+    {
+        if !Ty::is_directed() {
+            self.connect(from_id, to_id, weight);
+            return;
+        }
+
+        self.connect(from_id, to_id, weight.clone());
+        self.connect(to_id, from_id, weight);
+    }
+
+    /// Removes the node `id` together with every connection leading to or from it.
+    ///
+    /// The IDs of the remaining nodes stay valid and `id` won't be handed out again.
+    pub fn remove_node(&mut self, id: NodeID) -> Option<Node<N>> {
+        let node = self.nodes.remove(&id)?;
+        self.names.retain(|_, named| *named != id);
+
+        let outgoing = self.outgoing.remove(&id).unwrap_or_default();
+        let incoming = self.incoming.remove(&id).unwrap_or_default();
+        for index in outgoing.into_iter().chain(incoming) {
+            self.remove_connection(index);
+        }
+
+        Some(node)
+    }
+
+    /// Removes a single connection from `from_id` to `to_id` and returns its weight.
+    pub fn remove_edge(&mut self, from_id: NodeID, to_id: NodeID) -> Option<E> {
+        let index = self.find_connection(from_id, to_id)?;
+        self.remove_connection(index).map(|(_, _, weight)| weight)
+    }
+
+    /// Index of the first connection from `from_id` to `to_id`, in either
+    /// orientation for undirected graphs.
+    fn find_connection(&self, from_id: NodeID, to_id: NodeID) -> Option<usize> {
+        let leaving =
+            self.outgoing.get(&from_id).into_iter().flatten().find(
+                |&&index| matches!(self.connections[index], Some((_, t_id, _)) if t_id == to_id),
+            );
+        if leaving.is_some() || Ty::is_directed() {
+            return leaving.copied();
+        }
+
+        self.incoming
+            .get(&from_id)
+            .into_iter()
+            .flatten()
+            .find(|&&index| matches!(self.connections[index], Some((f_id, _, _)) if f_id == to_id))
+            .copied()
+    }
+
+    /// Removes every connection from `from_id` to `to_id`.
+    ///
+    /// Returns `true` if at least one connection was removed.
+    pub fn disconnect(&mut self, from_id: NodeID, to_id: NodeID) -> bool {
+        let mut removed = false;
+        while self.remove_edge(from_id, to_id).is_some() {
+            removed = true;
+        }
+        removed
+    }
+
+    /// Removes every connection between `from_id` and `to_id` in both directions.
+    pub fn disconnect_bidirectionally(&mut self, from_id: NodeID, to_id: NodeID) -> bool {
+        let removed = self.disconnect(from_id, to_id);
+        self.disconnect(to_id, from_id) || removed
+    }
+
+    /// Clears the connection at `index` and unlinks it from the adjacency lists.
+    ///
+    /// Returns `None` if the connection was already removed, which happens for
+    /// self-loops that show up in both lists of the same node.
+    fn remove_connection(&mut self, index: usize) -> Option<(NodeID, NodeID, E)> {
+        let (from_id, to_id, weight) = self.connections[index].take()?;
+        if let Some(list) = self.outgoing.get_mut(&from_id) {
+            list.retain(|&i| i != index);
+        }
+        if let Some(list) = self.incoming.get_mut(&to_id) {
+            list.retain(|&i| i != index);
+        }
+        Some((from_id, to_id, weight))
+    }
+
+    /// Number of connections leaving the node `id`.
+    ///
+    /// For undirected graphs this is the number of connections touching the node.
+    pub fn out_degree(&self, id: NodeID) -> usize {
+        if Ty::is_directed() {
+            self.outgoing.get(&id).map_or(0, Vec::len)
+        } else {
+            self.outgoing(id).count()
+        }
+    }
+
+    /// Number of connections entering the node `id`.
+    ///
+    /// For undirected graphs this is the number of connections touching the node.
+    pub fn in_degree(&self, id: NodeID) -> usize {
+        if Ty::is_directed() {
+            self.incoming.get(&id).map_or(0, Vec::len)
+        } else {
+            self.incoming(id).count()
+        }
+    }
+
+    /// Iterates over the connections leaving the node `id` in O(out-degree).
+    ///
+    /// Each item is oriented so that `id` comes first, also for undirected graphs.
+    pub fn outgoing(&self, id: NodeID) -> Connections<'_, E> {
+        Connections::new(
+            &self.connections,
+            id,
+            true,
+            self.outgoing.get(&id),
+            self.incoming.get(&id).filter(|_| !Ty::is_directed()),
+        )
+    }
+
+    /// Iterates over the connections entering the node `id` in O(in-degree).
+    ///
+    /// Each item is oriented so that `id` comes second, also for undirected graphs.
+    pub fn incoming(&self, id: NodeID) -> Connections<'_, E> {
+        Connections::new(
+            &self.connections,
+            id,
+            false,
+            self.incoming.get(&id),
+            self.outgoing.get(&id).filter(|_| !Ty::is_directed()),
+        )
+    }
+
+    /// Iterates over the nodes that can be reached from `id` over a single connection.
+    pub fn neighbors(&self, id: NodeID) -> Neighbors<'_, E> {
+        Neighbors {
+            inner: self.outgoing(id),
+        }
+    }
+
+    /// The weight of the first connection from `from_id` to `to_id`.
+    pub fn edge_weight(&self, from_id: NodeID, to_id: NodeID) -> Option<&E> {
+        self.outgoing(from_id)
+            .find(|&(_, t_id, _)| t_id == to_id)
+            .map(|(_, _, weight)| weight)
+    }
+
+    /// Whether there is a connection from `from_id` to `to_id`.
+    pub fn contains_edge(&self, from_id: NodeID, to_id: NodeID) -> bool {
+        self.find_connection(from_id, to_id).is_some()
+    }
+
+    pub fn get_node(&self, id: &NodeID) -> Option<&Node<N>> {
+        self.nodes.get(id)
+    }
+
+    pub fn get_node_mut(&mut self, id: &NodeID) -> Option<&mut Node<N>> {
+        self.nodes.get_mut(id)
+    }
+
+    pub fn iter<'a>(&'a self) -> Iter<'a, N> {
+        Iter {
+            inner: self.nodes.iter(),
+        }
+    }
+
+    /// Iterates over all connections as `(from, to, weight)` in the order they were made.
+    ///
+    /// Undirected connections are yielded once, in the orientation they were made in.
+    pub fn edges(&self) -> Edges<'_, E> {
+        Edges {
+            inner: self.connections.iter(),
+        }
+    }
+}
+
+impl<N, E, Ty> Graph<N, E, Ty>
+where
+    E: EdgeWeight,
+    Ty: EdgeType,
+{
+    /// Lets [`Graph::try_connect`] accept negative weights, which only
+    /// [`Graph::bellman_ford`] can handle.
+    pub fn allow_negative_weights(&mut self, allow: bool) {
+        self.allow_negative_weights = allow;
+    }
+
+    /// Like [`Graph::connect`], but rejects nodes that are not in the graph and
+    /// NaN, infinite and (unless allowed) negative weights.
+    pub fn try_connect(
+        &mut self,
+        from_id: NodeID,
+        to_id: NodeID,
+        weight: E,
+    ) -> Result<(), ConnectError> {
+        self.check_connection(from_id, to_id, &weight)?;
+        self.connect(from_id, to_id, weight);
+        Ok(())
+    }
+
+    /// Like [`Graph::connect_bidirectionally`], but checks the weight like [`Graph::try_connect`].
+    pub fn try_connect_bidirectionally(
+        &mut self,
+        from_id: NodeID,
+        to_id: NodeID,
+        weight: E,
+    ) -> Result<(), ConnectError>
+    where
+        E: Clone,
+    {
+        self.check_connection(from_id, to_id, &weight)?;
+        self.connect_bidirectionally(from_id, to_id, weight);
+        Ok(())
+    }
+
+    fn check_connection(
+        &self,
+        from_id: NodeID,
+        to_id: NodeID,
+        weight: &E,
+    ) -> Result<(), ConnectError> {
+        if let Some(id) = [from_id, to_id]
+            .into_iter()
+            .find(|id| !self.nodes.contains_key(id))
+        {
+            return Err(ConnectError::UnknownNode(id));
+        }
+        ConnectError::check(weight.weight(), self.allow_negative_weights)
+    }
+}
+
+impl<N, E, Ty> Default for Graph<N, E, Ty>
+where
+    Ty: EdgeType,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An iterator of the Nodes of a Graph in `NodeID` order
+pub struct Iter<'a, N> {
+    inner: btree_map::Iter<'a, NodeID, Node<N>>,
+}
+
+impl<'a, N> Iterator for Iter<'a, N> {
+    type Item = (&'a NodeID, &'a Node<N>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+/// An iterator of the connections of a Graph in the order they were made
+pub struct Edges<'a, E> {
+    inner: slice::Iter<'a, Option<(NodeID, NodeID, E)>>,
+}
+
+impl<'a, E> Iterator for Edges<'a, E> {
+    type Item = (NodeID, NodeID, &'a E);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner
+            .by_ref()
+            .flatten()
+            .map(|(from_id, to_id, weight)| (*from_id, *to_id, weight))
+            .next()
+    }
+}
+
+/// An iterator of the connections entering or leaving a node
+pub struct Connections<'a, E> {
+    connections: &'a [Option<(NodeID, NodeID, E)>],
+    /// the node whose connections are listed
+    id: NodeID,
+    /// whether items are oriented as leaving `id`
+    leaving: bool,
+    indices: slice::Iter<'a, usize>,
+    /// the opposite adjacency list, only set for undirected graphs
+    reversed: slice::Iter<'a, usize>,
+}
+
+impl<'a, E> Connections<'a, E> {
+    fn new(
+        connections: &'a [Option<(NodeID, NodeID, E)>],
+        id: NodeID,
+        leaving: bool,
+        indices: Option<&'a Vec<usize>>,
+        reversed: Option<&'a Vec<usize>>,
+    ) -> Self {
+        Self {
+            connections,
+            id,
+            leaving,
+            indices: indices.map_or(&[][..], Vec::as_slice).iter(),
+            reversed: reversed.map_or(&[][..], Vec::as_slice).iter(),
+        }
+    }
+}
+
+impl<'a, E> Iterator for Connections<'a, E> {
+    type Item = (NodeID, NodeID, &'a E);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (from_id, to_id, weight) = loop {
+            if let Some(&index) = self.indices.next() {
+                if let Some(connection) = &self.connections[index] {
+                    break connection;
+                }
+            } else {
+                let &index = self.reversed.next()?;
+                match &self.connections[index] {
+                    // self-loops are in both lists, they were already yielded
+                    Some((from_id, to_id, _)) if from_id == to_id => continue,
+                    Some(connection) => break connection,
+                    None => continue,
+                }
+            }
+        };
+
+        let anchor = if self.leaving { *from_id } else { *to_id };
+        if anchor == self.id {
+            Some((*from_id, *to_id, weight))
+        } else {
+            Some((*to_id, *from_id, weight))
+        }
+    }
+}
+
+/// An iterator of the neighbors of a node
+pub struct Neighbors<'a, E> {
+    inner: Connections<'a, E>,
+}
+
+impl<E> Iterator for Neighbors<'_, E> {
+    type Item = NodeID;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(_, to_id, _)| to_id)
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Node<T> {
+    /// value of this node
+    pub value: T,
+}
+
+impl<T> Node<T> {
+    pub fn new(value: impl Into<T>) -> Self {
+        Self {
+            value: value.into(),
+        }
+    }
+}
+
+impl<T> Default for Node<T>
+where
+    T: Default,
+{
+    fn default() -> Self {
+        Self {
+            value: Default::default(),
+        }
+    }
+}
+
+impl<T> From<T> for Node<T> {
+    fn from(value: T) -> Self {
+        Self { value }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct NodeID(i32);
+
+impl From<i32> for NodeID {
+    fn from(value: i32) -> Self {
+        Self(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ConnectError, Graph, Node, UnGraph};
+
+    #[test]
+    fn test_iteration_order_is_deterministic() {
+        let mut graph: Graph<i32> = Graph::new();
+        let ids: Vec<_> = (0..32).map(|i| graph.insert(Node::new(i))).collect();
+        for pair in ids.windows(2).rev() {
+            graph.connect(pair[1], pair[0], 1.0);
+        }
+        graph.remove_edge(ids[5], ids[4]);
+
+        let values: Vec<_> = graph.iter().map(|(_, node)| node.value).collect();
+        assert_eq!(values, (0..32).collect::<Vec<_>>());
+
+        let edges: Vec<_> = graph.edges().map(|(from, to, _)| (from, to)).collect();
+        let expected: Vec<_> = ids
+            .windows(2)
+            .rev()
+            .map(|pair| (pair[1], pair[0]))
+            .filter(|&edge| edge != (ids[5], ids[4]))
+            .collect();
+        assert_eq!(edges, expected);
+    }
+
+    #[test]
+    fn test_neighbor_queries() {
+        let mut graph: Graph<&str> = Graph::new();
+        let a = graph.insert(Node::new("a"));
+        let b = graph.insert(Node::new("b"));
+        let c = graph.insert(Node::new("c"));
+
+        graph.connect(a, b, 1.0);
+        graph.connect(a, c, 2.0);
+        graph.connect(c, b, 3.0);
+
+        assert_eq!(graph.neighbors(a).collect::<Vec<_>>(), vec![b, c]);
+        assert_eq!(
+            graph.incoming(b).collect::<Vec<_>>(),
+            vec![(a, b, &1.0), (c, b, &3.0)]
+        );
+        assert_eq!(graph.outgoing(c).collect::<Vec<_>>(), vec![(c, b, &3.0)]);
+        assert_eq!(graph.edge_weight(a, c), Some(&2.0));
+        assert!(graph.contains_edge(c, b));
+        assert!(!graph.contains_edge(b, c));
+        assert_eq!(graph.in_degree(b), 2);
+        assert_eq!(graph.out_degree(b), 0);
+    }
+
+    #[test]
+    fn test_undirected_graph_stores_edges_once() {
+        let mut graph: UnGraph<&str> = UnGraph::new();
+        let a = graph.insert(Node::new("a"));
+        let b = graph.insert(Node::new("b"));
+        let c = graph.insert(Node::new("c"));
+
+        graph.connect_bidirectionally(a, b, 1.0);
+        graph.connect(c, a, 2.0);
+        graph.connect(c, c, 3.0);
+
+        assert_eq!(graph.edges().count(), 3);
+        assert_eq!(graph.neighbors(a).collect::<Vec<_>>(), vec![b, c]);
+        assert_eq!(
+            graph.outgoing(a).collect::<Vec<_>>(),
+            vec![(a, b, &1.0), (a, c, &2.0)]
+        );
+        assert_eq!(
+            graph.outgoing(c).collect::<Vec<_>>(),
+            vec![(c, a, &2.0), (c, c, &3.0)]
+        );
+        assert_eq!(graph.in_degree(b), 1);
+        assert_eq!(graph.out_degree(c), 2);
+        assert_eq!(graph.edge_weight(b, a), Some(&1.0));
+
+        assert_eq!(graph.remove_edge(b, a), Some(1.0));
+        assert!(!graph.contains_edge(a, b));
+        assert_eq!(graph.edges().count(), 2);
+    }
+
+    #[test]
+    fn test_try_connect_validates_weights() {
+        let mut graph: Graph<&str> = Graph::new();
+        let a = graph.insert(Node::new("a"));
+        let b = graph.insert(Node::new("b"));
+
+        assert_eq!(
+            graph.try_connect(a, b, f64::NAN),
+            Err(ConnectError::NotANumber)
+        );
+        assert_eq!(
+            graph.try_connect(a, b, f64::INFINITY),
+            Err(ConnectError::Infinite(f64::INFINITY))
+        );
+        assert_eq!(
+            graph.try_connect(a, b, -1.0),
+            Err(ConnectError::Negative(-1.0))
+        );
+        assert_eq!(graph.edges().count(), 0);
+
+        graph.remove_node(b);
+        assert_eq!(
+            graph.try_connect(a, b, 1.0),
+            Err(ConnectError::UnknownNode(b))
+        );
+        assert_eq!(graph.edges().count(), 0);
+
+        let b = graph.insert(Node::new("b"));
+        graph.allow_negative_weights(true);
+        assert_eq!(graph.try_connect_bidirectionally(a, b, -1.0), Ok(()));
+        assert_eq!(graph.edges().count(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "is not in the graph")]
+    fn test_connect_rejects_removed_nodes() {
+        let mut graph: Graph<&str> = Graph::new();
+        let a = graph.insert(Node::new("a"));
+        let b = graph.insert(Node::new("b"));
+        graph.remove_node(b);
+        graph.connect(a, b, 1.0);
+    }
+
+    #[test]
+    fn test_remove_node_drops_incident_connections() {
+        let mut graph: Graph<&str> = Graph::new();
+        let a = graph.insert(Node::new("a"));
+        let b = graph.insert(Node::new("b"));
+        let c = graph.insert(Node::new("c"));
+
+        graph.connect_bidirectionally(a, b, 1.0);
+        graph.connect(b, c, 2.0);
+        graph.connect(b, b, 3.0);
+
+        assert_eq!(graph.remove_node(b).map(|node| node.value), Some("b"));
+        assert!(graph.get_node(&b).is_none());
+        assert_eq!(graph.out_degree(a), 0);
+        assert_eq!(graph.in_degree(c), 0);
+
+        // removed IDs are never handed out again
+        let d = graph.insert(Node::new("d"));
+        assert_ne!(d, b);
+        assert_eq!(graph.get_node(&c).map(|node| node.value), Some("c"));
+    }
+
+    #[test]
+    fn test_named_nodes() {
+        let mut graph: Graph<&str> = Graph::new();
+        let home = graph.insert_named("home", Node::new("Home"));
+        let school = graph.insert_named("school", Node::new("School"));
+        graph.insert(Node::new("Unnamed"));
+
+        assert_eq!(graph.id("home"), Some(home));
+        assert_eq!(graph.id("school"), Some(school));
+        assert_eq!(graph.id("Home"), None);
+
+        graph.remove_node(school);
+        assert_eq!(graph.id("school"), None);
+    }
+
+    #[test]
+    fn test_remove_edge_and_disconnect() {
+        let mut graph: Graph<&str> = Graph::new();
+        let a = graph.insert(Node::new("a"));
+        let b = graph.insert(Node::new("b"));
+
+        graph.connect(a, b, 1.0);
+        graph.connect(a, b, 2.0);
+        graph.connect(b, a, 3.0);
+
+        assert_eq!(graph.remove_edge(a, b), Some(1.0));
+        assert_eq!(graph.out_degree(a), 1);
+
+        assert!(graph.disconnect_bidirectionally(a, b));
+        assert_eq!(graph.out_degree(a), 0);
+        assert_eq!(graph.out_degree(b), 0);
+        assert!(!graph.disconnect(a, b));
+        assert_eq!(graph.remove_edge(a, b), None);
+    }
+}
@@ -0,0 +1,207 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error as DeError};
+
+use super::{EdgeType, Graph, Node, NodeID};
+
+/// How a graph looks when serialized, borrowing from the graph
+#[derive(Serialize)]
+struct GraphRef<'a, N, E> {
+    directed: bool,
+    nodes: Vec<NodeEntry<&'a N>>,
+    connections: Vec<(NodeID, NodeID, &'a E)>,
+    next_id: i32,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    names: &'a BTreeMap<String, NodeID>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    allow_negative_weights: bool,
+}
+
+/// How a graph looks when deserialized
+#[derive(Deserialize)]
+struct GraphRepr<N, E> {
+    directed: bool,
+    nodes: Vec<NodeEntry<N>>,
+    connections: Vec<(NodeID, NodeID, E)>,
+    next_id: i32,
+    #[serde(default)]
+    names: BTreeMap<String, NodeID>,
+    #[serde(default)]
+    allow_negative_weights: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+struct NodeEntry<N> {
+    id: NodeID,
+    value: N,
+}
+
+impl<N, E, Ty> Serialize for Graph<N, E, Ty>
+where
+    N: Serialize,
+    E: Serialize,
+    Ty: EdgeType,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        GraphRef {
+            directed: Ty::is_directed(),
+            nodes: self
+                .nodes
+                .iter()
+                .map(|(&id, node)| NodeEntry {
+                    id,
+                    value: &node.value,
+                })
+                .collect(),
+            connections: self.edges().collect(),
+            next_id: self.next_id,
+            names: &self.names,
+            allow_negative_weights: self.allow_negative_weights,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, N, E, Ty> Deserialize<'de> for Graph<N, E, Ty>
+where
+    N: Deserialize<'de>,
+    E: Deserialize<'de>,
+    Ty: EdgeType,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = GraphRepr::<N, E>::deserialize(deserializer)?;
+        if repr.directed != Ty::is_directed() {
+            return Err(D::Error::custom(format!(
+                "expected a {} graph",
+                if Ty::is_directed() {
+                    "directed"
+                } else {
+                    "undirected"
+                }
+            )));
+        }
+
+        if repr.next_id < 0 {
+            return Err(D::Error::custom(format!(
+                "`next_id` {} is negative",
+                repr.next_id
+            )));
+        }
+
+        let mut graph = Graph::new();
+        let mut nodes = BTreeMap::new();
+        for NodeEntry { id, value } in repr.nodes {
+            if id.0 < 0 {
+                return Err(D::Error::custom(format!("node {} is negative", id.0)));
+            }
+            if id.0 >= repr.next_id {
+                return Err(D::Error::custom(format!(
+                    "node {} is not below `next_id` {}",
+                    id.0, repr.next_id
+                )));
+            }
+            if nodes.insert(id, Node::from(value)).is_some() {
+                return Err(D::Error::custom(format!("duplicate node {}", id.0)));
+            }
+            graph.outgoing.insert(id, Vec::new());
+            graph.incoming.insert(id, Vec::new());
+        }
+        graph.nodes = nodes;
+        graph.next_id = repr.next_id;
+
+        for (from_id, to_id, weight) in repr.connections {
+            for id in [from_id, to_id] {
+                if !graph.nodes.contains_key(&id) {
+                    return Err(D::Error::custom(format!(
+                        "connection to unknown node {}",
+                        id.0
+                    )));
+                }
+            }
+            graph.connect(from_id, to_id, weight);
+        }
+
+        for (name, id) in &repr.names {
+            if !graph.nodes.contains_key(id) {
+                return Err(D::Error::custom(format!(
+                    "name `{name}` refers to unknown node {}",
+                    id.0
+                )));
+            }
+        }
+        graph.names = repr.names;
+        graph.allow_negative_weights = repr.allow_negative_weights;
+
+        Ok(graph)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{Graph, Node, NodeID, UnGraph};
+
+    #[test]
+    fn test_json_round_trip_keeps_ids() {
+        let mut graph: Graph<String> = Graph::new();
+        let home = graph.insert(Node::new("Home"));
+        let removed = graph.insert(Node::new("Removed"));
+        let school = graph.insert(Node::new("School"));
+        graph.connect(home, school, 23.0);
+        graph.connect(school, removed, 1.0);
+        graph.remove_node(removed);
+
+        let json = serde_json::to_string(&graph).unwrap();
+        assert_eq!(
+            json,
+            r#"{"directed":true,"nodes":[{"id":0,"value":"Home"},{"id":2,"value":"School"}],"connections":[[0,2,23.0]],"next_id":3}"#
+        );
+        let restored: Graph<String> = serde_json::from_str(&json).unwrap();
+        assert!(!restored.allow_negative_weights);
+
+        graph.allow_negative_weights(true);
+        let json = serde_json::to_string(&graph).unwrap();
+        assert!(json.ends_with(r#""next_id":3,"allow_negative_weights":true}"#));
+
+        let mut restored: Graph<String> = serde_json::from_str(&json).unwrap();
+        assert!(restored.allow_negative_weights);
+        assert_eq!(restored.try_connect(school, home, -1.0), Ok(()));
+        assert_eq!(
+            restored.get_node(&school).map(|node| node.value.as_str()),
+            Some("School")
+        );
+        assert_eq!(restored.edge_weight(home, school), Some(&23.0));
+        assert_eq!(restored.insert(Node::new("New")), NodeID::from(3));
+    }
+
+    #[test]
+    fn test_json_round_trip_keeps_names() {
+        let mut graph: UnGraph<String> = UnGraph::new();
+        let home = graph.insert_named("home", Node::new("Home"));
+        graph.insert(Node::new("Unnamed"));
+
+        let json = serde_json::to_string(&graph).unwrap();
+        assert!(json.ends_with(r#""next_id":2,"names":{"home":0}}"#));
+
+        let restored: UnGraph<String> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.id("home"), Some(home));
+    }
+
+    #[test]
+    fn test_deserialize_rejects_invalid_graphs() {
+        let unknown = r#"{"directed":true,"nodes":[{"id":0,"value":"a"}],"connections":[[0,1,1.0]],"next_id":1}"#;
+        assert!(serde_json::from_str::<Graph<String>>(unknown).is_err());
+
+        let directed = r#"{"directed":true,"nodes":[],"connections":[],"next_id":0}"#;
+        assert!(serde_json::from_str::<UnGraph<String>>(directed).is_err());
+
+        let name = r#"{"directed":true,"nodes":[],"connections":[],"next_id":0,"names":{"a":0}}"#;
+        assert!(serde_json::from_str::<Graph<String>>(name).is_err());
+
+        let negative =
+            r#"{"directed":true,"nodes":[{"id":-1,"value":"a"}],"connections":[],"next_id":1}"#;
+        assert!(serde_json::from_str::<Graph<String>>(negative).is_err());
+
+        let next_id = r#"{"directed":true,"nodes":[],"connections":[],"next_id":-1}"#;
+        assert!(serde_json::from_str::<Graph<String>>(next_id).is_err());
+    }
+}
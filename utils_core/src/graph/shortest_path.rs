@@ -0,0 +1,316 @@
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap},
+};
+
+use super::{EdgeType, EdgeWeight, Graph, Node, NodeID};
+
+/// The cost of and the predecessor on the cheapest path to every node found by a search
+pub(super) type SearchTree = (HashMap<NodeID, f64>, HashMap<NodeID, NodeID>);
+
+/// An entry of the priority queue used by the path searches
+#[derive(Copy, Clone, PartialEq)]
+struct State {
+    cost: f64,
+    position: NodeID,
+}
+
+impl Eq for State {}
+
+impl PartialOrd for State {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for State {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // smallest first for min-heap, ties are broken by the lower NodeID
+        other
+            .cost
+            .total_cmp(&self.cost)
+            .then_with(|| other.position.cmp(&self.position))
+    }
+}
+
+impl<N, E, Ty> Graph<N, E, Ty>
+where
+    E: EdgeWeight,
+    Ty: EdgeType,
+{
+    /// Finds the cheapest path from `from_id` to `to_id` using Dijkstra's algorithm.
+    ///
+    /// Returns the total cost and the path, starting with `from_id` and ending
+    /// with `to_id`, or `None` if `to_id` can't be reached.
+    /// Connection weights are expected to be non-negative.
+    pub fn shortest_path(&self, from_id: NodeID, to_id: NodeID) -> Option<(f64, Vec<NodeID>)> {
+        self.shortest_path_by(from_id, to_id, E::weight)
+    }
+
+    /// Finds the cheapest path from `from_id` to `to_id` using A* search.
+    ///
+    /// `heuristic` estimates the remaining cost from a node to `to_id`. As long as
+    /// it never overestimates that cost the result is the same as
+    /// [`Graph::shortest_path`], but fewer nodes have to be looked at. Nodes are
+    /// looked at again when a cheaper way to them turns up later, which only
+    /// happens if the estimate drops by more than a connection costs.
+    pub fn astar(
+        &self,
+        from_id: NodeID,
+        to_id: NodeID,
+        heuristic: impl Fn(&Node<N>) -> f64,
+    ) -> Option<(f64, Vec<NodeID>)> {
+        self.astar_by(from_id, to_id, E::weight, heuristic)
+    }
+}
+
+impl<N, E, Ty> Graph<N, E, Ty>
+where
+    Ty: EdgeType,
+{
+    /// Like [`Graph::shortest_path`], but takes the cost of a connection from `edge_cost`.
+    pub fn shortest_path_by(
+        &self,
+        from_id: NodeID,
+        to_id: NodeID,
+        edge_cost: impl Fn(&E) -> f64,
+    ) -> Option<(f64, Vec<NodeID>)> {
+        self.astar_by(from_id, to_id, edge_cost, |_| 0.0)
+    }
+
+    /// Like [`Graph::astar`], but takes the cost of a connection from `edge_cost`.
+    pub fn astar_by(
+        &self,
+        from_id: NodeID,
+        to_id: NodeID,
+        edge_cost: impl Fn(&E) -> f64,
+        heuristic: impl Fn(&Node<N>) -> f64,
+    ) -> Option<(f64, Vec<NodeID>)> {
+        if !self.nodes.contains_key(&from_id) || !self.nodes.contains_key(&to_id) {
+            return None;
+        }
+
+        let mut dist: HashMap<NodeID, f64> = HashMap::new();
+        let mut prev: HashMap<NodeID, NodeID> = HashMap::new();
+        // the estimate of the latest heap entry of every node
+        let mut estimates: HashMap<NodeID, f64> = HashMap::new();
+        let mut heap = BinaryHeap::new();
+
+        // the heap is ordered by the cost so far plus the estimate for the rest
+        let start_estimate = heuristic(&self.nodes[&from_id]);
+        dist.insert(from_id, 0.0);
+        estimates.insert(from_id, start_estimate);
+        heap.push(State {
+            cost: start_estimate,
+            position: from_id,
+        });
+
+        while let Some(State {
+            cost: estimate,
+            position,
+        }) = heap.pop()
+        {
+            // a cheaper way to this node was found after this entry was queued
+            if estimate > estimates[&position] {
+                continue;
+            }
+
+            let cost = dist[&position];
+            if position == to_id {
+                return Some((cost, reconstruct_path(&prev, from_id, to_id)));
+            }
+
+            for (_, next, weight) in self.outgoing(position) {
+                let Some(node) = self.nodes.get(&next) else {
+                    continue;
+                };
+
+                let next_cost = cost + edge_cost(weight);
+                if dist.get(&next).is_none_or(|&current| next_cost < current) {
+                    let next_estimate = next_cost + heuristic(node);
+                    dist.insert(next, next_cost);
+                    prev.insert(next, position);
+                    estimates.insert(next, next_estimate);
+                    heap.push(State {
+                        cost: next_estimate,
+                        position: next,
+                    });
+                }
+            }
+        }
+
+        None
+    }
+}
+
+impl<N, E, Ty> Graph<N, E, Ty>
+where
+    Ty: EdgeType,
+{
+    /// Dijkstra's algorithm without a target, returning the cost of and the
+    /// predecessor on the cheapest path to every node reachable from `source`.
+    ///
+    /// `edge_cost` gets the connection as `(from, to, weight)`, walked from `from` to `to`.
+    pub(super) fn dijkstra_from_by(
+        &self,
+        source: NodeID,
+        edge_cost: &impl Fn(NodeID, NodeID, &E) -> f64,
+    ) -> SearchTree {
+        let mut dist: HashMap<NodeID, f64> = HashMap::from([(source, 0.0)]);
+        let mut prev: HashMap<NodeID, NodeID> = HashMap::new();
+        let mut heap = BinaryHeap::from([State {
+            cost: 0.0,
+            position: source,
+        }]);
+
+        while let Some(State { cost, position }) = heap.pop() {
+            // a cheaper way to this node has already been handled
+            if cost > dist[&position] {
+                continue;
+            }
+
+            for (_, next, weight) in self.outgoing(position) {
+                let next_cost = cost + edge_cost(position, next, weight);
+                if dist.get(&next).is_none_or(|&current| next_cost < current) {
+                    dist.insert(next, next_cost);
+                    prev.insert(next, position);
+                    heap.push(State {
+                        cost: next_cost,
+                        position: next,
+                    });
+                }
+            }
+        }
+
+        (dist, prev)
+    }
+}
+
+/// Walks the predecessor map back from `to_id` and returns the path in travel order.
+fn reconstruct_path(prev: &HashMap<NodeID, NodeID>, from_id: NodeID, to_id: NodeID) -> Vec<NodeID> {
+    let mut path = vec![to_id];
+    let mut current = to_id;
+    while current != from_id {
+        current = prev[&current];
+        path.push(current);
+    }
+    path.reverse();
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::graph::{Graph, Node};
+
+    #[test]
+    fn test_shortest_path_prefers_cheaper_detour() {
+        let mut graph: Graph<&str> = Graph::new();
+        let a = graph.insert(Node::new("a"));
+        let b = graph.insert(Node::new("b"));
+        let c = graph.insert(Node::new("c"));
+        let d = graph.insert(Node::new("d"));
+
+        graph.connect(a, d, 10.0);
+        graph.connect(a, b, 2.0);
+        graph.connect(b, c, 3.0);
+        graph.connect(c, d, 1.0);
+
+        assert_eq!(graph.shortest_path(a, d), Some((6.0, vec![a, b, c, d])));
+        assert_eq!(graph.shortest_path(a, a), Some((0.0, vec![a])));
+    }
+
+    #[test]
+    fn test_shortest_path_unreachable() {
+        let mut graph: Graph<&str> = Graph::new();
+        let a = graph.insert(Node::new("a"));
+        let b = graph.insert(Node::new("b"));
+
+        graph.connect(b, a, 1.0);
+
+        assert_eq!(graph.shortest_path(a, b), None);
+    }
+
+    #[test]
+    fn test_shortest_path_by_custom_edge() {
+        #[derive(Debug)]
+        enum Mode {
+            Walk(f64),
+            Train(f64),
+        }
+
+        let mut graph: Graph<&str, Mode> = Graph::new();
+        let a = graph.insert(Node::new("a"));
+        let b = graph.insert(Node::new("b"));
+        let c = graph.insert(Node::new("c"));
+
+        graph.connect(a, c, Mode::Walk(10.0));
+        graph.connect(a, b, Mode::Train(8.0));
+        graph.connect(b, c, Mode::Walk(1.0));
+
+        // trains are four times as fast as walking
+        let travel_time = |mode: &Mode| match mode {
+            Mode::Walk(distance) => *distance,
+            Mode::Train(distance) => distance / 4.0,
+        };
+
+        assert_eq!(
+            graph.shortest_path_by(a, c, travel_time),
+            Some((3.0, vec![a, b, c]))
+        );
+    }
+
+    #[test]
+    fn test_astar_on_grid() {
+        // 4x4 grid with unit weights, node values are the coordinates
+        let mut graph: Graph<(i32, i32)> = Graph::new();
+        let mut ids = HashMap::new();
+        for x in 0..4 {
+            for y in 0..4 {
+                ids.insert((x, y), graph.insert(Node::new((x, y))));
+            }
+        }
+        for (&(x, y), &id) in &ids {
+            for neighbor in [(x + 1, y), (x, y + 1)] {
+                if let Some(&other) = ids.get(&neighbor) {
+                    graph.connect_bidirectionally(id, other, 1.0);
+                }
+            }
+        }
+
+        let goal = (3, 3);
+        let manhattan = |node: &Node<(i32, i32)>| {
+            let (x, y) = node.value;
+            ((goal.0 - x).abs() + (goal.1 - y).abs()) as f64
+        };
+
+        let (cost, path) = graph.astar(ids[&(0, 0)], ids[&goal], manhattan).unwrap();
+        assert_eq!(cost, 6.0);
+        assert_eq!(path.len(), 7);
+        assert_eq!(
+            graph
+                .shortest_path(ids[&(0, 0)], ids[&goal])
+                .map(|(cost, _)| cost),
+            Some(cost)
+        );
+    }
+
+    #[test]
+    fn test_astar_with_inconsistent_heuristic() {
+        // never overestimates, but drops by more than S -> A costs
+        let mut graph: Graph<&str> = Graph::new();
+        let s = graph.insert(Node::new("S"));
+        let a = graph.insert(Node::new("A"));
+        let b = graph.insert(Node::new("B"));
+        let g = graph.insert(Node::new("G"));
+        graph.connect(s, b, 3.0);
+        graph.connect(s, a, 1.0);
+        graph.connect(a, b, 1.0);
+        graph.connect(b, g, 3.0);
+
+        let heuristic = |node: &Node<&str>| if node.value == "A" { 4.0 } else { 0.0 };
+        assert_eq!(graph.astar(s, g, heuristic), Some((5.0, vec![s, a, b, g])));
+        assert_eq!(graph.astar(s, g, heuristic), graph.shortest_path(s, g));
+    }
+}
@@ -0,0 +1,222 @@
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashSet},
+};
+
+use super::{EdgeType, EdgeWeight, Graph, Node, NodeID, UnGraph, union_find::UnionFind};
+
+/// The connections picked for a minimum spanning forest, one tree per connected component.
+#[derive(Debug, Clone)]
+pub struct SpanningForest<E> {
+    /// the picked connections as `(from, to, weight)`
+    pub edges: Vec<(NodeID, NodeID, E)>,
+    /// sum of the weights of all picked connections
+    pub total_weight: f64,
+}
+
+impl<E> SpanningForest<E>
+where
+    E: Clone,
+{
+    /// Builds an undirected graph with every node of `graph`, under its original
+    /// `NodeID` and name, and only the connections of this forest.
+    pub fn to_graph<N, Ty>(&self, graph: &Graph<N, E, Ty>) -> UnGraph<N, E>
+    where
+        N: Clone,
+        Ty: EdgeType,
+    {
+        let mut tree = UnGraph::new();
+        for (&id, node) in graph.iter() {
+            tree.nodes.insert(id, Node::new(node.value.clone()));
+            tree.outgoing.insert(id, Vec::new());
+            tree.incoming.insert(id, Vec::new());
+        }
+        tree.next_id = graph.next_id;
+        tree.names = graph.names.clone();
+
+        for (from_id, to_id, weight) in &self.edges {
+            tree.connect(*from_id, *to_id, weight.clone());
+        }
+        tree
+    }
+}
+
+/// A connection waiting in Prim's priority queue
+struct Candidate<'a, E> {
+    cost: f64,
+    /// insertion order, so equal costs are picked deterministically
+    order: usize,
+    from: NodeID,
+    to: NodeID,
+    weight: &'a E,
+}
+
+impl<E> PartialEq for Candidate<'_, E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<E> Eq for Candidate<'_, E> {}
+
+impl<E> PartialOrd for Candidate<'_, E> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<E> Ord for Candidate<'_, E> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // smallest first for min-heap
+        other
+            .cost
+            .total_cmp(&self.cost)
+            .then_with(|| other.order.cmp(&self.order))
+    }
+}
+
+impl<N, E, Ty> Graph<N, E, Ty>
+where
+    E: EdgeWeight + Clone,
+    Ty: EdgeType,
+{
+    /// Finds a minimum spanning forest with Kruskal's algorithm, ignoring directions.
+    ///
+    /// Connections with equal weights are picked in the order they were made.
+    pub fn kruskal(&self) -> SpanningForest<E> {
+        let mut edges: Vec<(NodeID, NodeID, &E)> = self.edges().collect();
+        edges.sort_by(|(_, _, a), (_, _, b)| a.weight().total_cmp(&b.weight()));
+
+        let mut sets = UnionFind::new();
+        let mut forest = SpanningForest {
+            edges: Vec::new(),
+            total_weight: 0.0,
+        };
+        for (from_id, to_id, weight) in edges {
+            if sets.union(from_id, to_id) {
+                forest.total_weight += weight.weight();
+                forest.edges.push((from_id, to_id, weight.clone()));
+            }
+        }
+        forest
+    }
+
+    /// Finds a minimum spanning forest with Prim's algorithm, ignoring directions.
+    ///
+    /// Every tree is grown from the lowest `NodeID` of its component.
+    pub fn prim(&self) -> SpanningForest<E> {
+        let mut visited: HashSet<NodeID> = HashSet::new();
+        let mut forest = SpanningForest {
+            edges: Vec::new(),
+            total_weight: 0.0,
+        };
+
+        for &root in self.nodes.keys() {
+            if !visited.insert(root) {
+                continue;
+            }
+
+            let mut order = 0;
+            let mut heap = BinaryHeap::new();
+            let mut push_incident = |id: NodeID, heap: &mut BinaryHeap<_>| {
+                for (to, weight) in self.incident(id) {
+                    heap.push(Candidate {
+                        cost: weight.weight(),
+                        order,
+                        from: id,
+                        to,
+                        weight,
+                    });
+                    order += 1;
+                }
+            };
+
+            push_incident(root, &mut heap);
+            while let Some(candidate) = heap.pop() {
+                if !visited.insert(candidate.to) {
+                    continue;
+                }
+
+                forest.total_weight += candidate.cost;
+                forest
+                    .edges
+                    .push((candidate.from, candidate.to, candidate.weight.clone()));
+                push_incident(candidate.to, &mut heap);
+            }
+        }
+
+        forest
+    }
+
+    /// Builds the minimum spanning forest of [`Graph::kruskal`] as a graph that
+    /// keeps the original `NodeID`s.
+    pub fn minimum_spanning_tree(&self) -> UnGraph<N, E>
+    where
+        N: Clone,
+    {
+        self.kruskal().to_graph(self)
+    }
+
+    /// Every connection touching `id` in either direction, as `(other node, weight)`.
+    fn incident(&self, id: NodeID) -> impl Iterator<Item = (NodeID, &E)> {
+        let leaving = self.outgoing(id).map(|(_, to_id, weight)| (to_id, weight));
+        // the outgoing connections of undirected graphs already contain everything
+        let entering = self
+            .incoming(id)
+            .filter(|_| Ty::is_directed())
+            .map(|(from_id, _, weight)| (from_id, weight));
+        leaving
+            .chain(entering)
+            .filter(move |&(other, _)| other != id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{Graph, Node, UnGraph};
+
+    #[test]
+    fn test_kruskal_and_prim_agree() {
+        let mut graph: UnGraph<&str> = UnGraph::new();
+        let a = graph.insert(Node::new("a"));
+        let b = graph.insert(Node::new("b"));
+        let c = graph.insert(Node::new("c"));
+        let d = graph.insert(Node::new("d"));
+        let lonely = graph.insert(Node::new("lonely"));
+
+        graph.connect(a, b, 1.0);
+        graph.connect(b, c, 4.0);
+        graph.connect(a, c, 3.0);
+        graph.connect(c, d, 2.0);
+        graph.connect(b, d, 5.0);
+
+        let kruskal = graph.kruskal();
+        let prim = graph.prim();
+        assert_eq!(kruskal.total_weight, 6.0);
+        assert_eq!(prim.total_weight, 6.0);
+        assert_eq!(kruskal.edges, vec![(a, b, 1.0), (c, d, 2.0), (a, c, 3.0)]);
+        assert_eq!(prim.edges, vec![(a, b, 1.0), (a, c, 3.0), (c, d, 2.0)]);
+
+        let tree = graph.minimum_spanning_tree();
+        assert_eq!(tree.edges().count(), 3);
+        assert_eq!(
+            tree.get_node(&lonely).map(|node| node.value),
+            Some("lonely")
+        );
+        assert!(tree.contains_edge(d, c));
+    }
+
+    #[test]
+    fn test_spanning_forest_of_directed_graph() {
+        let mut graph: Graph<&str> = Graph::new();
+        let a = graph.insert(Node::new("a"));
+        let b = graph.insert(Node::new("b"));
+        let c = graph.insert(Node::new("c"));
+
+        graph.connect_bidirectionally(a, b, 2.0);
+        graph.connect(c, a, 1.0);
+
+        assert_eq!(graph.prim().total_weight, 3.0);
+        assert_eq!(graph.kruskal().edges, vec![(c, a, 1.0), (a, b, 2.0)]);
+    }
+}
@@ -0,0 +1,158 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet},
+};
+
+use thiserror::Error;
+
+use super::{Directed, Graph, NodeID, Undirected, union_find::UnionFind};
+
+/// Returned when an ordering is requested for a graph that contains a cycle.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Graph contains a cycle: {nodes:?}")]
+pub struct Cycle {
+    /// the nodes of one cycle in walking order, the last one connects back to the first
+    pub nodes: Vec<NodeID>,
+}
+
+impl<N, E> Graph<N, E, Directed> {
+    /// Orders the nodes so that every connection leads from an earlier to a later node.
+    ///
+    /// Uses Kahn's algorithm, and whenever several nodes are ready the one with the
+    /// lowest `NodeID` comes first, so the order is the same for every run.
+    pub fn toposort(&self) -> Result<Vec<NodeID>, Cycle> {
+        let mut in_degrees: HashMap<NodeID, usize> = self
+            .nodes
+            .keys()
+            .map(|&id| (id, self.in_degree(id)))
+            .collect();
+
+        let mut ready: BinaryHeap<Reverse<NodeID>> = in_degrees
+            .iter()
+            .filter(|&(_, &degree)| degree == 0)
+            .map(|(&id, _)| Reverse(id))
+            .collect();
+
+        let mut order = Vec::with_capacity(self.nodes.len());
+        let mut sorted = HashSet::new();
+        while let Some(Reverse(id)) = ready.pop() {
+            order.push(id);
+            sorted.insert(id);
+
+            for next in self.neighbors(id) {
+                let Some(degree) = in_degrees.get_mut(&next) else {
+                    continue;
+                };
+                *degree -= 1;
+                if *degree == 0 {
+                    ready.push(Reverse(next));
+                }
+            }
+        }
+
+        if order.len() == self.nodes.len() {
+            return Ok(order);
+        }
+
+        Err(self.find_cycle(&sorted))
+    }
+
+    /// Whether any node can be reached from itself.
+    pub fn is_cyclic(&self) -> bool {
+        self.toposort().is_err()
+    }
+
+    /// Finds a cycle among the nodes Kahn's algorithm couldn't sort.
+    ///
+    /// Every unsorted node still has an unsorted predecessor, so walking backwards
+    /// over those has to run into a node that was already seen.
+    fn find_cycle(&self, sorted: &HashSet<NodeID>) -> Cycle {
+        let start = *self
+            .nodes
+            .keys()
+            .find(|id| !sorted.contains(id))
+            .expect("an unsorted node exists if the order is incomplete");
+
+        let mut path = Vec::new();
+        let mut positions: HashMap<NodeID, usize> = HashMap::new();
+        let mut current = start;
+        while !positions.contains_key(&current) {
+            positions.insert(current, path.len());
+            path.push(current);
+            current = self
+                .incoming(current)
+                .map(|(from_id, _, _)| from_id)
+                .find(|from_id| !sorted.contains(from_id))
+                .expect("an unsorted node has an unsorted predecessor");
+        }
+
+        // the path was walked backwards, turn it around so it starts at `current`
+        let mut nodes = path.split_off(positions[&current]);
+        nodes.reverse();
+        nodes.rotate_right(1);
+        Cycle { nodes }
+    }
+}
+
+impl<N, E> Graph<N, E, Undirected> {
+    /// Whether any node can be reached from itself without walking a connection twice.
+    pub fn is_cyclic(&self) -> bool {
+        let mut sets = UnionFind::new();
+        self.edges()
+            .any(|(from_id, to_id, _)| !sets.union(from_id, to_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Cycle;
+    use crate::graph::{Graph, Node, UnGraph};
+
+    #[test]
+    fn test_toposort_breaks_ties_by_id() {
+        let mut graph: Graph<&str> = Graph::new();
+        let compile = graph.insert(Node::new("compile"));
+        let fetch = graph.insert(Node::new("fetch"));
+        let lint = graph.insert(Node::new("lint"));
+        let test = graph.insert(Node::new("test"));
+
+        graph.connect(fetch, compile, 1.0);
+        graph.connect(compile, test, 1.0);
+        graph.connect(fetch, lint, 1.0);
+
+        assert_eq!(graph.toposort(), Ok(vec![fetch, compile, lint, test]));
+        assert!(!graph.is_cyclic());
+    }
+
+    #[test]
+    fn test_toposort_reports_cycle() {
+        let mut graph: Graph<&str> = Graph::new();
+        let a = graph.insert(Node::new("a"));
+        let b = graph.insert(Node::new("b"));
+        let c = graph.insert(Node::new("c"));
+        let d = graph.insert(Node::new("d"));
+
+        graph.connect(a, b, 1.0);
+        graph.connect(b, c, 1.0);
+        graph.connect(c, b, 1.0);
+        graph.connect(c, d, 1.0);
+
+        assert_eq!(graph.toposort(), Err(Cycle { nodes: vec![b, c] }));
+        assert!(graph.is_cyclic());
+    }
+
+    #[test]
+    fn test_undirected_cycle() {
+        let mut graph: UnGraph<&str> = UnGraph::new();
+        let a = graph.insert(Node::new("a"));
+        let b = graph.insert(Node::new("b"));
+        let c = graph.insert(Node::new("c"));
+
+        graph.connect(a, b, 1.0);
+        graph.connect(b, c, 1.0);
+        assert!(!graph.is_cyclic());
+
+        graph.connect(c, a, 1.0);
+        assert!(graph.is_cyclic());
+    }
+}
@@ -0,0 +1,286 @@
+use std::{
+    collections::{HashSet, VecDeque},
+    marker::PhantomData,
+};
+
+use super::{EdgeType, Graph, NodeID};
+
+/// A traversal that only borrows the graph for each step, so node values can be
+/// changed through [`Graph::get_node_mut`] between two calls to `walk_next`.
+pub trait Walker<N, E, Ty>
+where
+    Ty: EdgeType,
+{
+    type Item;
+
+    /// Advances the walk by one step.
+    fn walk_next(&mut self, graph: &Graph<N, E, Ty>) -> Option<Self::Item>;
+
+    /// Turns the walker into an iterator that borrows `graph` for its whole lifetime.
+    fn iter(self, graph: &Graph<N, E, Ty>) -> WalkerIter<'_, Self, N, E, Ty>
+    where
+        Self: Sized,
+    {
+        WalkerIter {
+            walker: self,
+            graph,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// An iterator over the steps of a [`Walker`]
+pub struct WalkerIter<'a, W, N, E, Ty> {
+    walker: W,
+    graph: &'a Graph<N, E, Ty>,
+    _marker: PhantomData<fn() -> (N, E)>,
+}
+
+impl<W, N, E, Ty> Iterator for WalkerIter<'_, W, N, E, Ty>
+where
+    W: Walker<N, E, Ty>,
+    Ty: EdgeType,
+{
+    type Item = W::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.walker.walk_next(self.graph)
+    }
+}
+
+/// Breadth-first walk over the nodes reachable from a start node.
+#[derive(Debug, Clone)]
+pub struct Bfs {
+    queue: VecDeque<NodeID>,
+    discovered: HashSet<NodeID>,
+}
+
+impl Bfs {
+    pub fn new(start: NodeID) -> Self {
+        Self {
+            queue: VecDeque::from([start]),
+            discovered: HashSet::from([start]),
+        }
+    }
+
+    /// Restarts the walk from `start`, forgetting every node visited so far.
+    pub fn reset(&mut self, start: NodeID) {
+        self.queue.clear();
+        self.discovered.clear();
+        self.queue.push_back(start);
+        self.discovered.insert(start);
+    }
+
+    /// Returns the next node in breadth-first order.
+    pub fn next<N, E, Ty: EdgeType>(&mut self, graph: &Graph<N, E, Ty>) -> Option<NodeID> {
+        let id = self.queue.pop_front()?;
+        for next in graph.neighbors(id) {
+            if self.discovered.insert(next) {
+                self.queue.push_back(next);
+            }
+        }
+        Some(id)
+    }
+}
+
+impl<N, E, Ty: EdgeType> Walker<N, E, Ty> for Bfs {
+    type Item = NodeID;
+
+    fn walk_next(&mut self, graph: &Graph<N, E, Ty>) -> Option<Self::Item> {
+        self.next(graph)
+    }
+}
+
+/// A step of a depth-first walk
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DfsEvent {
+    /// The node is reached for the first time (pre-order).
+    Discover(NodeID),
+    /// Everything reachable through the node has been visited (post-order).
+    Finish(NodeID),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum DfsStep {
+    Visit(NodeID),
+    Finish(NodeID),
+}
+
+/// Depth-first walk over the nodes reachable from a start node.
+#[derive(Debug, Clone)]
+pub struct Dfs {
+    stack: Vec<DfsStep>,
+    discovered: HashSet<NodeID>,
+}
+
+impl Dfs {
+    pub fn new(start: NodeID) -> Self {
+        Self {
+            stack: vec![DfsStep::Visit(start)],
+            discovered: HashSet::new(),
+        }
+    }
+
+    /// Restarts the walk from `start`, forgetting every node visited so far.
+    pub fn reset(&mut self, start: NodeID) {
+        self.discovered.clear();
+        self.move_to(start);
+    }
+
+    /// Continues the walk from `start`, skipping nodes that were already visited.
+    pub fn move_to(&mut self, start: NodeID) {
+        self.stack.clear();
+        self.stack.push(DfsStep::Visit(start));
+    }
+
+    /// Whether `id` has been discovered by this walk.
+    pub fn is_discovered(&self, id: NodeID) -> bool {
+        self.discovered.contains(&id)
+    }
+
+    /// Returns the next discover or finish event.
+    pub fn next_event<N, E, Ty: EdgeType>(&mut self, graph: &Graph<N, E, Ty>) -> Option<DfsEvent> {
+        while let Some(step) = self.stack.pop() {
+            match step {
+                DfsStep::Visit(id) => {
+                    if !self.discovered.insert(id) {
+                        continue;
+                    }
+
+                    self.stack.push(DfsStep::Finish(id));
+                    // pushed in reverse so the first connection is walked first
+                    let successors: Vec<NodeID> = graph
+                        .neighbors(id)
+                        .filter(|next| !self.discovered.contains(next))
+                        .collect();
+                    self.stack
+                        .extend(successors.into_iter().rev().map(DfsStep::Visit));
+
+                    return Some(DfsEvent::Discover(id));
+                }
+                DfsStep::Finish(id) => return Some(DfsEvent::Finish(id)),
+            }
+        }
+
+        None
+    }
+
+    /// Returns the next node in depth-first pre-order.
+    pub fn next<N, E, Ty: EdgeType>(&mut self, graph: &Graph<N, E, Ty>) -> Option<NodeID> {
+        loop {
+            if let DfsEvent::Discover(id) = self.next_event(graph)? {
+                return Some(id);
+            }
+        }
+    }
+}
+
+impl<N, E, Ty: EdgeType> Walker<N, E, Ty> for Dfs {
+    type Item = DfsEvent;
+
+    fn walk_next(&mut self, graph: &Graph<N, E, Ty>) -> Option<Self::Item> {
+        self.next_event(graph)
+    }
+}
+
+/// Depth-first walk that yields each node after everything reachable through it.
+#[derive(Debug, Clone)]
+pub struct DfsPostOrder {
+    dfs: Dfs,
+}
+
+impl DfsPostOrder {
+    pub fn new(start: NodeID) -> Self {
+        Self {
+            dfs: Dfs::new(start),
+        }
+    }
+
+    /// Restarts the walk from `start`, forgetting every node visited so far.
+    pub fn reset(&mut self, start: NodeID) {
+        self.dfs.reset(start);
+    }
+
+    /// Continues the walk from `start`, skipping nodes that were already visited.
+    pub fn move_to(&mut self, start: NodeID) {
+        self.dfs.move_to(start);
+    }
+
+    /// Returns the next node in depth-first post-order.
+    pub fn next<N, E, Ty: EdgeType>(&mut self, graph: &Graph<N, E, Ty>) -> Option<NodeID> {
+        loop {
+            if let DfsEvent::Finish(id) = self.dfs.next_event(graph)? {
+                return Some(id);
+            }
+        }
+    }
+}
+
+impl<N, E, Ty: EdgeType> Walker<N, E, Ty> for DfsPostOrder {
+    type Item = NodeID;
+
+    fn walk_next(&mut self, graph: &Graph<N, E, Ty>) -> Option<Self::Item> {
+        self.next(graph)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Bfs, Dfs, DfsEvent, DfsPostOrder, Walker};
+    use crate::graph::{Graph, Node};
+
+    #[test]
+    fn test_bfs_and_dfs_order() {
+        // a -> b -> d -> a
+        //  \-> c
+        let mut graph: Graph<i32> = Graph::new();
+        let a = graph.insert(Node::new(0));
+        let b = graph.insert(Node::new(0));
+        let c = graph.insert(Node::new(0));
+        let d = graph.insert(Node::new(0));
+
+        graph.connect(a, b, 1.0);
+        graph.connect(a, c, 1.0);
+        graph.connect(b, d, 1.0);
+        graph.connect(d, a, 1.0);
+
+        assert_eq!(
+            Bfs::new(a).iter(&graph).collect::<Vec<_>>(),
+            vec![a, b, c, d]
+        );
+        assert_eq!(
+            Dfs::new(a).iter(&graph).collect::<Vec<_>>(),
+            vec![
+                DfsEvent::Discover(a),
+                DfsEvent::Discover(b),
+                DfsEvent::Discover(d),
+                DfsEvent::Finish(d),
+                DfsEvent::Finish(b),
+                DfsEvent::Discover(c),
+                DfsEvent::Finish(c),
+                DfsEvent::Finish(a),
+            ]
+        );
+        assert_eq!(
+            DfsPostOrder::new(a).iter(&graph).collect::<Vec<_>>(),
+            vec![d, b, c, a]
+        );
+    }
+
+    #[test]
+    fn test_walk_while_mutating_nodes() {
+        let mut graph: Graph<i32> = Graph::new();
+        let a = graph.insert(Node::new(0));
+        let b = graph.insert(Node::new(0));
+        graph.connect(a, b, 1.0);
+
+        let mut depth = 0;
+        let mut dfs = Dfs::new(a);
+        while let Some(id) = dfs.next(&graph) {
+            depth += 1;
+            graph.get_node_mut(&id).unwrap().value = depth;
+        }
+
+        assert_eq!(graph.get_node(&b).map(|node| node.value), Some(2));
+    }
+}
@@ -0,0 +1,54 @@
+use std::collections::HashMap;
+
+use super::NodeID;
+
+/// Disjoint sets of nodes, used to find out which nodes are connected.
+#[derive(Debug, Default)]
+pub(super) struct UnionFind {
+    parent: HashMap<NodeID, NodeID>,
+    rank: HashMap<NodeID, usize>,
+}
+
+impl UnionFind {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The representative of the set containing `id`.
+    pub fn find(&mut self, id: NodeID) -> NodeID {
+        let parent = *self.parent.entry(id).or_insert(id);
+        if parent == id {
+            return id;
+        }
+
+        let root = self.find(parent);
+        self.parent.insert(id, root);
+        root
+    }
+
+    /// Merges the sets of `a` and `b`.
+    ///
+    /// Returns `false` if both were already in the same set.
+    pub fn union(&mut self, a: NodeID, b: NodeID) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+
+        let rank_a = self.rank.get(&a).copied().unwrap_or(0);
+        let rank_b = self.rank.get(&b).copied().unwrap_or(0);
+        match rank_a.cmp(&rank_b) {
+            std::cmp::Ordering::Less => {
+                self.parent.insert(a, b);
+            }
+            std::cmp::Ordering::Greater => {
+                self.parent.insert(b, a);
+            }
+            std::cmp::Ordering::Equal => {
+                self.parent.insert(b, a);
+                self.rank.insert(a, rank_a + 1);
+            }
+        }
+        true
+    }
+}
@@ -1,5 +1,5 @@
 use syn::{
-    Expr, Ident, LitFloat, Result, Token,
+    Attribute, Error as SynError, Expr, Ident, Result, Token,
     parse::{Parse, ParseStream},
     punctuated::Punctuated,
     token,
@@ -28,60 +28,181 @@ impl Parse for NodeDef {
     }
 }
 
+/// One side of a connection, a single node or a `{ a, b, ... }` group
 #[derive(Debug)]
-pub(super) enum ConnectionDef {
-    Directed {
-        from: Ident,
-        _arrow_token: Token![->],
-        to: Ident,
-        weight: Option<(Token![:], LitFloat)>,
-    },
-    Undirected {
-        from: Ident,
-        _double_arrow_token: DoubleArrow,
-        to: Ident,
-        weight: Option<(Token![:], LitFloat)>,
+pub(super) enum Endpoint {
+    Node(Ident),
+    Group {
+        _brace_token: token::Brace,
+        nodes: Punctuated<Ident, Token![,]>,
     },
 }
 
-impl Parse for ConnectionDef {
+impl Endpoint {
+    pub fn nodes(&self) -> Vec<&Ident> {
+        match self {
+            Self::Node(name) => vec![name],
+            Self::Group { nodes, .. } => nodes.iter().collect(),
+        }
+    }
+}
+
+impl Parse for Endpoint {
     fn parse(input: ParseStream) -> Result<Self> {
-        let from: Ident = input.parse()?;
+        if input.peek(token::Brace) {
+            let content;
+            let brace_token = syn::braced!(content in input);
+            let nodes = content.parse_terminated(Ident::parse, Token![,])?;
+            if nodes.is_empty() {
+                return Err(SynError::new(brace_token.span.join(), "empty node group"));
+            }
 
-        if input.peek(Token![->]) {
-            let arrow_token = input.parse()?;
-            let to = input.parse()?;
+            Ok(Self::Group {
+                _brace_token: brace_token,
+                nodes,
+            })
+        } else {
+            Ok(Self::Node(input.parse()?))
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(super) enum Arrow {
+    Directed { _arrow_token: Token![->] },
+    Undirected { _double_arrow_token: DoubleArrow },
+}
 
-            let weight = if input.peek(Token![:]) {
-                Some((input.parse()?, input.parse()?))
+impl Arrow {
+    pub fn is_undirected(&self) -> bool {
+        matches!(self, Self::Undirected { .. })
+    }
+}
+
+/// `a -> b`, chained as `a -> b <-> c` and fanned out or in as `a -> {b, c}`
+/// or `{a, b} -> c`, with an optional weight for every connection of the chain
+#[derive(Debug)]
+pub(super) struct ConnectionDef {
+    pub first: Endpoint,
+    /// every arrow with the endpoint following it
+    pub hops: Vec<(Arrow, Endpoint)>,
+    pub weight: Option<(Token![:], Expr)>,
+}
+
+impl ConnectionDef {
+    /// Every connection of the chain as `(from, to, is_undirected)`, each node
+    /// before an arrow paired with each node after it
+    pub fn pairs(&self) -> Vec<(&Ident, &Ident, bool)> {
+        let mut pairs = Vec::new();
+        let mut previous = &self.first;
+        for (arrow, endpoint) in &self.hops {
+            for from in previous.nodes() {
+                for to in endpoint.nodes() {
+                    pairs.push((from, to, arrow.is_undirected()));
+                }
+            }
+            previous = endpoint;
+        }
+        pairs
+    }
+}
+
+impl Parse for ConnectionDef {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let first = input.parse()?;
+
+        let mut hops = Vec::new();
+        loop {
+            let arrow = if input.peek(Token![->]) {
+                Arrow::Directed {
+                    _arrow_token: input.parse()?,
+                }
+            } else if input.peek(DoubleArrow) {
+                Arrow::Undirected {
+                    _double_arrow_token: input.parse()?,
+                }
             } else {
-                None
+                break;
             };
+            hops.push((arrow, input.parse()?));
+        }
+        if hops.is_empty() {
+            return Err(input.error("expected `->` or `<->` connection"));
+        }
 
-            Ok(Self::Directed {
-                from,
-                _arrow_token: arrow_token,
-                to,
-                weight,
-            })
-        } else if input.peek(DoubleArrow) {
-            let double_arrow_token = input.parse()?;
-            let to = input.parse()?;
+        let weight = if input.peek(Token![:]) {
+            Some((input.parse()?, input.parse()?))
+        } else {
+            None
+        };
+
+        Ok(Self {
+            first,
+            hops,
+            weight,
+        })
+    }
+}
 
-            let weight = if input.peek(Token![:]) {
-                Some((input.parse()?, input.parse()?))
+/// `#![...]` options at the start of the macro
+#[derive(Debug, Default)]
+pub(super) struct Options {
+    /// `#![implicit_nodes]`: connections may name undeclared nodes, which are
+    /// inserted with their `Default` value
+    pub implicit_nodes: bool,
+    /// `#![strict]`: lints are errors instead of warnings
+    pub strict: bool,
+    /// `#![check_dead_ends]`: also lint nodes without outgoing connections
+    pub check_dead_ends: bool,
+}
+
+impl Parse for Options {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut options = Self::default();
+        for attribute in input.call(Attribute::parse_inner)? {
+            let path = attribute.meta.require_path_only()?;
+            if path.is_ident("implicit_nodes") {
+                options.implicit_nodes = true;
+            } else if path.is_ident("strict") {
+                options.strict = true;
+            } else if path.is_ident("check_dead_ends") {
+                options.check_dead_ends = true;
             } else {
-                None
-            };
+                return Err(SynError::new_spanned(path, "unknown `graph!` option"));
+            }
+        }
+        Ok(options)
+    }
+}
 
-            Ok(Self::Undirected {
-                from,
-                _double_arrow_token: double_arrow_token,
-                to,
-                weight,
-            })
+#[derive(Debug)]
+pub(super) struct MacroInput {
+    pub options: Options,
+    pub graph: GraphInput,
+}
+
+impl Parse for MacroInput {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            options: input.parse()?,
+            graph: input.parse()?,
+        })
+    }
+}
+
+/// An entry of the comma separated form, either a node or a connection
+#[derive(Debug)]
+pub(super) enum GraphItem {
+    Node(NodeDef),
+    Connection(ConnectionDef),
+}
+
+impl Parse for GraphItem {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(Ident) && input.peek2(Token![=]) {
+            Ok(Self::Node(input.parse()?))
         } else {
-            Err(input.error("expected `->` or `<->` connection"))
+            Ok(Self::Connection(input.parse()?))
         }
     }
 }
@@ -89,21 +210,21 @@ impl Parse for ConnectionDef {
 #[derive(Debug)]
 pub(super) enum GraphInput {
     Empty,
-    SimpleNodes(Punctuated<NodeDef, Token![,]>),
+    SimpleNodes(Punctuated<GraphItem, Token![,]>),
     Structured {
         // nodes: { ... },
-        _nodes_keyword: Ident,
-        _nodes_colon: Token![:],
-        _nodes_brace_open: token::Brace,
+        _nodes_keyword: Option<Ident>,
+        _nodes_colon: Option<Token![:]>,
+        _nodes_brace_open: Option<token::Brace>,
         // ident = expr, ident = expr, ...
         nodes: Punctuated<NodeDef, Token![;]>,
-        _nodes_brace_close: token::Brace,
+        _nodes_brace_close: Option<token::Brace>,
 
         // connections: { ... },
         _connections_keyword: Option<Ident>,
         _connections_colon: Option<Token![:]>,
         _connections_brace_open: Option<token::Brace>,
-        /// ident -> ident, ident <-> ident, ...
+        /// ident -> ident, ident <-> ident -> {ident, ident}, ...
         connections: Punctuated<ConnectionDef, Token![;]>,
         _connections_brace_close: Option<token::Brace>,
     },
@@ -118,15 +239,23 @@ impl Parse for GraphInput {
         let fork = input.fork();
         if fork.peek(Ident) && fork.peek2(Token![:]) && fork.peek3(token::Brace) {
             let first_ident: Ident = fork.parse()?;
-            if first_ident.to_string() == "nodes" {
-                let nodes_keyword: Ident = input.parse()?;
-                let nodes_colon: Token![:] = input.parse()?;
-                let nodes_conent;
-                let nodes_brace_open = syn::braced!(nodes_conent in input);
-                // parse complete content of braces
-                // nodes: { <content> }
-                let nodes = nodes_conent.parse_terminated(NodeDef::parse, Token![;])?;
-                let nodes_brace_close = nodes_brace_open;
+            if first_ident == "nodes" || first_ident == "connections" {
+                let mut nodes_keyword = None;
+                let mut nodes_colon = None;
+                let mut nodes_brace_open = None;
+                let mut nodes = Punctuated::new();
+                let mut nodes_brace_close = None;
+
+                if first_ident == "nodes" {
+                    nodes_keyword = Some(input.parse()?);
+                    nodes_colon = Some(input.parse()?);
+                    let nodes_conent;
+                    nodes_brace_open = Some(syn::braced!(nodes_conent in input));
+                    // parse complete content of braces
+                    // nodes: { <content> }
+                    nodes = nodes_conent.parse_terminated(NodeDef::parse, Token![;])?;
+                    nodes_brace_close = nodes_brace_open;
+                }
 
                 let mut connections_keyword = None;
                 let mut connections_colon = None;
@@ -137,7 +266,7 @@ impl Parse for GraphInput {
                 if input.peek(Ident) {
                     let fork = input.fork();
                     let conn_ident: Ident = fork.parse()?;
-                    if conn_ident.to_string() == "connections" {
+                    if conn_ident == "connections" {
                         connections_keyword = Some(input.parse()?);
                         connections_colon = Some(input.parse()?);
                         let connections_content;
@@ -165,7 +294,7 @@ impl Parse for GraphInput {
             }
         }
 
-        let simple_nodes = input.parse_terminated(NodeDef::parse, Token![,])?;
+        let simple_nodes = input.parse_terminated(GraphItem::parse, Token![,])?;
         Ok(GraphInput::SimpleNodes(simple_nodes))
     }
 }
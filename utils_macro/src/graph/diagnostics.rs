@@ -0,0 +1,125 @@
+use std::fmt::Display;
+
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{ToTokens, quote, quote_spanned};
+use syn::Error as SynError;
+
+/// Collects every error and warning of a macro invocation so they can be
+/// reported together.
+pub(super) struct Diagnostics {
+    errors: Option<SynError>,
+    warnings: TokenStream2,
+    /// lints are errors under `#![strict]`
+    strict: bool,
+}
+
+impl Diagnostics {
+    pub fn new(strict: bool) -> Self {
+        Self {
+            errors: None,
+            warnings: TokenStream2::new(),
+            strict,
+        }
+    }
+
+    pub fn error(&mut self, tokens: impl ToTokens, message: impl Display) {
+        self.push(SynError::new_spanned(tokens, message));
+    }
+
+    pub fn push(&mut self, error: SynError) {
+        match &mut self.errors {
+            Some(errors) => errors.combine(error),
+            None => self.errors = Some(error),
+        }
+    }
+
+    /// Reports a likely mistake at `span`, as a warning or under `#![strict]`
+    /// as an error.
+    ///
+    /// Proc macros can't emit warnings on stable, so the warning is the call of a
+    /// deprecated function at `span`, which `#[allow(deprecated)]` silences.
+    pub fn lint(&mut self, span: Span, message: impl Display) {
+        if self.strict {
+            self.push(SynError::new(span, message));
+            return;
+        }
+
+        let note = message.to_string();
+        self.warnings.extend(quote_spanned! {span=>
+            {
+                #[deprecated(note = #note)]
+                fn graph_lint() {}
+                graph_lint();
+            }
+        });
+    }
+
+    /// The collected warnings, to be placed in the generated code.
+    pub fn warnings(&self) -> &TokenStream2 {
+        &self.warnings
+    }
+
+    /// All collected errors as a block with one `compile_error!` per error,
+    /// or `None` if there were none.
+    pub fn to_compile_errors(&self) -> Option<TokenStream2> {
+        let errors = self.errors.as_ref()?.to_compile_error();
+        Some(quote! {{ #errors }})
+    }
+}
+
+/// The candidate closest to `name`, if it is close enough to be a typo.
+pub(super) fn did_you_mean<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    // roughly one typo per three characters, like rustc, but never a whole
+    // different name for very short ones
+    let length = name.chars().count();
+    let max_distance = (length / 3).max(1).min(length - 1);
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|&(distance, _)| distance <= max_distance)
+        .min()
+        .map(|(_, candidate)| candidate)
+}
+
+/// The Levenshtein distance between `a` and `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, a_char) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, &b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::did_you_mean;
+
+    #[test]
+    fn test_did_you_mean() {
+        let names = [
+            "home",
+            "train_station_near_home",
+            "train_station_near_school",
+        ];
+
+        assert_eq!(
+            did_you_mean("train_staton_near_home", names),
+            Some("train_station_near_home")
+        );
+        assert_eq!(did_you_mean("hom", names), Some("home"));
+        assert_eq!(did_you_mean("school", names), None);
+        assert_eq!(did_you_mean("a", ["b"]), None);
+    }
+}
@@ -1,148 +1,204 @@
 use std::collections::HashSet;
 
-use super::ast::{ConnectionDef, GraphInput};
+use super::{
+    ast::{ConnectionDef, Endpoint, GraphInput, GraphItem, MacroInput},
+    diagnostics::{Diagnostics, did_you_mean},
+    lints::lint,
+};
 use proc_macro::TokenStream;
-use quote::quote;
-use syn::Error as SynError;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{quote, quote_spanned};
+use syn::{Expr, ExprLit, ExprUnary, Ident, Lit, UnOp, spanned::Spanned};
 
-pub fn expand(input: GraphInput) -> TokenStream {
-    let mut generated_output = quote! {
-        use ::utils::graph::{Graph, Node, NodeID};
-        use ::std::collections::HashMap;
+/// The connection weight, passed through as is so type errors point at it.
+///
+/// Unsuffixed integer literals like `a -> b: 3` would only fit integer weights,
+/// so they go through `IntegerWeight`, which lets floats convert them and leaves
+/// them to inference otherwise.
+fn connection_value(weight: &Expr) -> TokenStream2 {
+    if is_integer_literal(weight) {
+        quote_spanned! {weight.span()=> ::utils::graph::IntegerWeight::from_literal(#weight) }
+    } else {
+        quote! { #weight }
+    }
+}
 
-        let mut graph = Graph::new();
-    };
+/// Expands a connection chain into one `connect` call per pair of nodes.
+///
+/// Every arrow connects each node before it with each node after it. The weight
+/// is evaluated once, before any binding of the expansion, and cloned for every
+/// connection but the last.
+fn expand_connection(conn_def: &ConnectionDef) -> TokenStream2 {
+    let internal = Span::mixed_site();
+    let pairs = conn_def.pairs();
 
-    match input {
-        GraphInput::Empty => {
-            // Nothing extra to generate
+    // located at the weight, so type errors still point at it
+    let weight = conn_def
+        .weight
+        .as_ref()
+        .map(|(_, expr)| Ident::new("weight", internal.located_at(expr.span())));
+    let mut output = match (&conn_def.weight, &weight) {
+        (Some((_, expr)), Some(weight)) => {
+            let value = connection_value(expr);
+            quote_spanned! {internal=> let #weight = #value; }
         }
-        GraphInput::SimpleNodes(nodes) => {
-            let mut node_inserts = quote! {};
-            let mut node_names: HashSet<String> = HashSet::new();
-            for node_def in nodes {
-                let name = &node_def.name;
-                let value = &node_def.value;
-                let name_str = name.to_string();
-
-                if !node_names.insert(name_str.clone()) {
-                    return SynError::new_spanned(
-                        name,
-                        format!("Duplicate node identifier: `{}`", name_str),
-                    )
-                    .to_compile_error()
-                    .into();
-                }
+        _ => quote! {},
+    };
 
-                node_inserts.extend(quote! {
-                    graph.insert(::utils::graph::Node::new(#value));
-                });
+    let last = pairs.len() - 1;
+    for (index, (from_ident, to_ident, is_undirected)) in pairs.into_iter().enumerate() {
+        let weight_expr = match &weight {
+            Some(weight) if index == last => quote! { #weight },
+            Some(weight) => quote_spanned! {weight.span()=> ::core::clone::Clone::clone(&#weight) },
+            None => quote_spanned! {to_ident.span()=> ::utils::graph::UnitEdge::unit() },
+        };
+
+        let connect_fn = if is_undirected {
+            quote! { connect_bidirectionally }
+        } else {
+            quote! { connect }
+        };
+
+        output.extend(quote_spanned! {internal=>
+            let from_id = graph.id(stringify!(#from_ident)).expect("Internal macro error: Node not found after compile-time check.");
+            let to_id = graph.id(stringify!(#to_ident)).expect("Internal macro error: Node not found after compile-time check.");
+            graph.#connect_fn(from_id, to_id, #weight_expr);
+        });
+    }
+
+    quote! {{ #output }}
+}
+
+/// Whether `expr` is an integer literal without suffix, possibly negated.
+fn is_integer_literal(expr: &Expr) -> bool {
+    let expr = match expr {
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => expr,
+        expr => expr,
+    };
+    matches!(
+        expr,
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(literal),
+            ..
+        }) if literal.suffix().is_empty()
+    )
+}
+
+pub fn expand(input: MacroInput) -> TokenStream {
+    // everything the expansion binds is hidden from the user's expressions
+    let internal = Span::mixed_site();
+    let mut generated_output = quote! {};
+
+    let (nodes, connections, in_nodes_block) = match input.graph {
+        GraphInput::Empty => (Vec::new(), Vec::new(), false),
+        GraphInput::SimpleNodes(items) => {
+            let mut nodes = Vec::new();
+            let mut connections = Vec::new();
+            for item in items {
+                match item {
+                    GraphItem::Node(node_def) => nodes.push(node_def),
+                    GraphItem::Connection(conn_def) => connections.push(conn_def),
+                }
             }
-            generated_output.extend(node_inserts);
+            (nodes, connections, false)
         }
         GraphInput::Structured {
             nodes, connections, ..
-        } => {
-            let id_map_init = quote! {
-                let mut id_map: HashMap<&'static str, NodeID> = HashMap::new();
-            };
-            let mut node_inserts = quote! {};
-            let mut node_names: HashSet<String> = HashSet::new();
-            for node_def in nodes {
-                let name = &node_def.name;
-                let value = &node_def.value;
-                let name_str = name.to_string();
-
-                if !node_names.insert(name_str.clone()) {
-                    return SynError::new_spanned(
-                        name,
-                        format!("Duplicate node identifier: `{}`)", name_str),
-                    )
-                    .to_compile_error()
-                    .into();
-                }
+        } => (
+            nodes.into_iter().collect(),
+            connections.into_iter().collect(),
+            true,
+        ),
+    };
 
-                node_inserts.extend(quote! {
-                    let node_id = graph.insert(Node::new(#value));
-                    id_map.insert(stringify!(#name), node_id);
-                });
-            }
+    let mut diagnostics = Diagnostics::new(input.options.strict);
+    let mut node_inserts = quote! {};
+    let mut node_names: HashSet<String> = HashSet::new();
+    let mut node_idents: Vec<&Ident> = Vec::new();
+    for node_def in &nodes {
+        let name = &node_def.name;
+        let value = &node_def.value;
+        let name_str = name.to_string();
 
-            generated_output.extend(id_map_init);
-            generated_output.extend(node_inserts);
-
-            let mut connection_inserts = quote! {};
-            for conn_def in connections {
-                let (from_ident, to_ident, weight_expr, is_undirected) = match conn_def {
-                    ConnectionDef::Directed {
-                        from,
-                        _arrow_token,
-                        to,
-                        weight,
-                    } => {
-                        let weight_val = weight
-                            .clone()
-                            .map(|(_, l)| l.base10_parse().unwrap_or(1.0))
-                            .unwrap_or(1.0);
-                        (from, to, quote! { #weight_val }, false)
-                    }
-                    ConnectionDef::Undirected {
-                        from,
-                        _double_arrow_token,
-                        to,
-                        weight,
-                    } => {
-                        let weight_val = weight
-                            .clone()
-                            .map(|(_, l)| l.base10_parse().unwrap_or(1.0))
-                            .unwrap_or(1.0);
-                        (from, to, quote! { #weight_val }, true)
-                    }
-                };
-
-                // --- Compile-time check for node existence ---
-                let from_name_str = from_ident.to_string();
-                if !node_names.contains(&from_name_str) {
-                    return SynError::new_spanned(
-                        from_ident,
-                        format!("Node '{}' not defined in `nodes` block.", from_name_str),
-                    )
-                    .to_compile_error()
-                    .into();
-                }
-                let to_name_str = to_ident.to_string();
-                if !node_names.contains(&to_name_str) {
-                    return SynError::new_spanned(
-                        to_ident,
-                        format!("Node '{}' not defined in `nodes` block.", to_name_str),
-                    )
-                    .to_compile_error()
-                    .into();
-                }
-                // --- End compile-time check ---
+        if !node_names.insert(name_str.clone()) {
+            diagnostics.error(name, format!("Duplicate node identifier: `{}`", name_str));
+            continue;
+        }
 
-                connection_inserts.extend(quote! {
-                    let from_id = *id_map.get(stringify!(#from_ident)).expect("Internal macro error: Node not found after compile-time check.");
-                    let to_id = *id_map.get(stringify!(#to_ident)).expect("Internal macro error: Node not found after compile-time check.");
-                    graph.connect(from_id, to_id, #weight_expr);
-                });
+        node_idents.push(name);
+        node_inserts.extend(quote_spanned! {internal=>
+            graph.insert_named(stringify!(#name), ::utils::graph::Node::new(#value));
+        });
+    }
 
-                if is_undirected {
-                    connection_inserts.extend(quote! {
-                        let from_id = *id_map.get(stringify!(#from_ident)).expect("Internal macro error: Node not found after compile-time check.");
-                        let to_id = *id_map.get(stringify!(#to_ident)).expect("Internal macro error: Node not found after compile-time check.");
-                        graph.connect(to_id, from_id, #weight_expr);
-                    });
-                }
+    let declared: Vec<String> = nodes
+        .iter()
+        .map(|node_def| node_def.name.to_string())
+        .collect();
+    let mut connection_inserts = quote! {};
+    for conn_def in &connections {
+        // --- Compile-time check for node existence ---
+        let endpoints = std::iter::once(&conn_def.first)
+            .chain(conn_def.hops.iter().map(|(_, endpoint)| endpoint));
+        for name in endpoints.flat_map(Endpoint::nodes) {
+            let name_str = name.to_string();
+            if node_names.contains(&name_str) {
+                continue;
             }
 
-            generated_output.extend(connection_inserts);
+            // undeclared nodes go after the declared ones, in order of appearance
+            if input.options.implicit_nodes {
+                node_names.insert(name_str);
+                node_idents.push(name);
+                node_inserts.extend(quote_spanned! {internal=>
+                    graph.insert_named(stringify!(#name), ::utils::graph::Node::default());
+                });
+                continue;
+            }
+
+            let mut message = if in_nodes_block {
+                format!("Node '{}' not defined in `nodes` block.", name_str)
+            } else {
+                format!("Node '{}' not defined.", name_str)
+            };
+            if let Some(suggestion) = did_you_mean(&name_str, declared.iter().map(String::as_str)) {
+                message.push_str(&format!(" Did you mean `{}`?", suggestion));
+            }
+            diagnostics.error(name, message);
         }
+        // --- End compile-time check ---
+
+        connection_inserts.extend(expand_connection(conn_def));
+    }
+
+    for (span, message) in lint(&node_idents, &connections, input.options.check_dead_ends) {
+        diagnostics.lint(span, message);
+    }
+
+    if let Some(errors) = diagnostics.to_compile_errors() {
+        return errors.into();
     }
 
-    quote! {{
-        #generated_output
-        graph
-    }}
+    generated_output.extend(node_inserts);
+    generated_output.extend(connection_inserts);
+    generated_output.extend(diagnostics.warnings().clone());
+
+    // the break that is never taken hands the graph type the caller expects to
+    // `graph` before any weight is checked, so a wrong one is reported at its
+    // own span
+    quote_spanned! {internal=>
+        'graph: {
+            let mut graph = ::utils::graph::Graph::new();
+            if false {
+                break 'graph graph;
+            }
+            #generated_output
+            graph
+        }
+    }
     .into()
 }
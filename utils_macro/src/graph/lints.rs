@@ -0,0 +1,151 @@
+use std::collections::{HashMap, HashSet};
+
+use proc_macro2::Span;
+use syn::Ident;
+
+use super::ast::ConnectionDef;
+
+/// Finds connections that are most likely mistakes: self-loops, connections
+/// declared twice, `a -> b` next to `a <-> b` and, if there are connections at
+/// all, nodes without any. With `check_dead_ends` nodes without outgoing
+/// connections are reported as well.
+///
+/// Returns the span and message of every finding in order of appearance.
+pub(super) fn lint(
+    nodes: &[&Ident],
+    connections: &[ConnectionDef],
+    check_dead_ends: bool,
+) -> Vec<(Span, String)> {
+    let mut findings = Vec::new();
+    // every declared `from -> to` and `a <-> b`, the latter with sorted names,
+    // mapped to how it was written
+    let mut directed: HashMap<(String, String), String> = HashMap::new();
+    let mut undirected: HashMap<(String, String), String> = HashMap::new();
+    let mut connected: HashSet<String> = HashSet::new();
+    let mut outgoing: HashSet<String> = HashSet::new();
+
+    for (from, to, is_undirected) in connections.iter().flat_map(ConnectionDef::pairs) {
+        let (from, to, span) = (from.to_string(), to.to_string(), to.span());
+        if from == to {
+            findings.push((span, format!("`{from}` is connected to itself")));
+        }
+
+        let arrow = if is_undirected { "<->" } else { "->" };
+        let written = format!("{from} {arrow} {to}");
+        let sorted = if from <= to {
+            (from.clone(), to.clone())
+        } else {
+            (to.clone(), from.clone())
+        };
+        let earlier = if is_undirected {
+            undirected.get(&sorted).or_else(|| {
+                directed
+                    .get(&(from.clone(), to.clone()))
+                    .or_else(|| directed.get(&(to.clone(), from.clone())))
+            })
+        } else {
+            directed
+                .get(&(from.clone(), to.clone()))
+                .or_else(|| undirected.get(&sorted))
+        };
+        match earlier {
+            Some(earlier) if *earlier == written => {
+                findings.push((span, format!("`{written}` is declared twice")));
+            }
+            Some(earlier) => {
+                findings.push((span, format!("`{written}` repeats the earlier `{earlier}`")));
+            }
+            None => {}
+        }
+
+        if is_undirected {
+            undirected.entry(sorted).or_insert(written);
+            outgoing.insert(to.clone());
+        } else {
+            directed
+                .entry((from.clone(), to.clone()))
+                .or_insert(written);
+        }
+        outgoing.insert(from.clone());
+        connected.extend([from, to]);
+    }
+
+    // a graph without connections is just a list of nodes
+    if connections.is_empty() {
+        return findings;
+    }
+
+    for node in nodes {
+        let name = node.to_string();
+        if !connected.contains(&name) {
+            findings.push((node.span(), format!("node `{name}` has no connections")));
+        } else if check_dead_ends && !outgoing.contains(&name) {
+            findings.push((
+                node.span(),
+                format!("node `{name}` has no outgoing connections"),
+            ));
+        }
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::Span;
+    use syn::Ident;
+
+    use super::lint;
+    use crate::graph::ast::ConnectionDef;
+
+    fn messages(nodes: &[&str], connections: &[&str], check_dead_ends: bool) -> Vec<String> {
+        let nodes: Vec<Ident> = nodes
+            .iter()
+            .map(|name| Ident::new(name, Span::call_site()))
+            .collect();
+        let connections: Vec<ConnectionDef> = connections
+            .iter()
+            .map(|connection| syn::parse_str(connection).unwrap())
+            .collect();
+        let nodes: Vec<&Ident> = nodes.iter().collect();
+
+        lint(&nodes, &connections, check_dead_ends)
+            .into_iter()
+            .map(|(_, message)| message)
+            .collect()
+    }
+
+    #[test]
+    fn test_lint_connections() {
+        assert_eq!(
+            messages(
+                &["a", "b", "c", "d"],
+                &["a -> b", "a -> {b, c}", "c <-> a", "c -> a", "d -> d"],
+                false
+            ),
+            [
+                "`a -> b` is declared twice",
+                "`c <-> a` repeats the earlier `a -> c`",
+                "`c -> a` repeats the earlier `c <-> a`",
+                "`d` is connected to itself",
+            ]
+        );
+        assert!(messages(&["a", "b"], &["a -> b", "b -> a"], false).is_empty());
+    }
+
+    #[test]
+    fn test_lint_nodes() {
+        assert_eq!(
+            messages(&["a", "b", "c", "d"], &["a -> b", "c <-> a"], true),
+            [
+                "node `b` has no outgoing connections",
+                "node `d` has no connections"
+            ]
+        );
+        assert_eq!(
+            messages(&["a", "b", "c"], &["a -> b"], false),
+            ["node `c` has no connections"]
+        );
+        assert!(messages(&["a", "b"], &[], true).is_empty());
+    }
+}
@@ -1,11 +1,13 @@
 mod ast;
+mod diagnostics;
 mod expand;
+mod lints;
 
-use ast::GraphInput;
+use ast::MacroInput;
 use proc_macro::TokenStream;
 use syn::parse_macro_input;
 
 pub(super) fn graph_impl(input: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(input as GraphInput);
+    let input = parse_macro_input!(input as MacroInput);
     expand::expand(input)
 }